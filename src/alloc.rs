@@ -0,0 +1,188 @@
+#[cfg(feature = "count-allocations")]
+use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(feature = "count-allocations")]
+use std::cell::Cell;
+
+/// What a piece of code allocated while it was being tracked
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Allocations {
+    /// Number of allocations, counting each reallocation as a fresh one
+    pub count: usize,
+    /// Total bytes asked for, whether or not they were freed again
+    pub bytes: usize,
+    /// Most bytes live at any one time, over and above whatever was live when tracking started
+    pub peak: usize,
+}
+
+#[cfg(feature = "count-allocations")]
+#[derive(Clone, Copy)]
+struct Tracking {
+    enabled: bool,
+    count: usize,
+    bytes: usize,
+    live: isize,
+    peak: isize,
+}
+
+#[cfg(feature = "count-allocations")]
+impl Tracking {
+    const OFF: Tracking = Tracking {
+        enabled: false,
+        count: 0,
+        bytes: 0,
+        live: 0,
+        peak: 0,
+    };
+
+    const ON: Tracking = Tracking {
+        enabled: true,
+        ..Tracking::OFF
+    };
+}
+
+#[cfg(feature = "count-allocations")]
+thread_local! {
+    // Counted per thread so that tests running in parallel don't pollute each other's numbers.
+    // It's const initialised and has no destructor, so touching it never allocates itself.
+    static TRACKING: Cell<Tracking> = const { Cell::new(Tracking::OFF) };
+}
+
+#[cfg(feature = "count-allocations")]
+fn record(allocated: usize, freed: usize) {
+    let _ = TRACKING.try_with(|tracking| {
+        let mut t = tracking.get();
+        if t.enabled {
+            t.count += 1;
+            t.bytes += allocated;
+            t.live += allocated as isize - freed as isize;
+            t.peak = t.peak.max(t.live);
+            tracking.set(t);
+        }
+    });
+}
+
+#[cfg(feature = "count-allocations")]
+fn record_free(freed: usize) {
+    let _ = TRACKING.try_with(|tracking| {
+        let mut t = tracking.get();
+        if t.enabled {
+            // Freeing memory allocated before tracking started can take this below zero
+            t.live -= freed as isize;
+            tracking.set(t);
+        }
+    });
+}
+
+/// The system allocator, keeping count of what's allocated on threads that are inside `track`
+///
+/// Even outside of `track` every allocation checks a thread local flag, so it's only installed
+/// when built with the `count-allocations` feature and ordinary runs and benchmarks don't pay for
+/// it.
+#[cfg(feature = "count-allocations")]
+pub struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_free(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Run `f`, counting everything it allocates on the current thread
+#[cfg(feature = "count-allocations")]
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let outer = TRACKING.with(|t| t.replace(Tracking::ON));
+    let result = f();
+    let tracked = TRACKING.with(|t| t.replace(outer));
+
+    (
+        result,
+        Allocations {
+            count: tracked.count,
+            bytes: tracked.bytes,
+            peak: tracked.peak.max(0) as usize,
+        },
+    )
+}
+
+/// Without the counting allocator there's nothing to count with
+#[cfg(not(feature = "count-allocations"))]
+pub fn track<T>(_f: impl FnOnce() -> T) -> (T, Allocations) {
+    panic!("Counting allocations needs building with --features count-allocations")
+}
+
+#[cfg(feature = "count-allocations")]
+#[test]
+fn test_nothing_allocated() {
+    let (sum, allocations) = track(|| (1..=10).sum::<u32>());
+    assert_eq!(55, sum);
+    assert_eq!(Allocations::default(), allocations);
+}
+
+#[cfg(feature = "count-allocations")]
+#[test]
+fn test_peak_is_most_live_at_once() {
+    let (_, allocations) = track(|| {
+        drop(vec![0u8; 1000]);
+        drop(vec![0u8; 500]);
+    });
+    assert_eq!(
+        Allocations {
+            count: 2,
+            bytes: 1500,
+            peak: 1000,
+        },
+        allocations
+    );
+
+    let (kept, allocations) = track(|| (vec![0u8; 1000], vec![0u8; 500]));
+    assert_eq!(1500, allocations.peak);
+    drop(kept);
+}
+
+#[cfg(feature = "count-allocations")]
+#[test]
+fn test_reallocation_counts_growth() {
+    let (_, allocations) = track(|| {
+        let mut v: Vec<u8> = Vec::with_capacity(100);
+        v.reserve_exact(200);
+        v
+    });
+    assert_eq!(
+        Allocations {
+            count: 2,
+            bytes: 300,
+            peak: 200,
+        },
+        allocations
+    );
+}
@@ -1,3 +1,5 @@
+use rng::Rng;
+
 /// --- Day 1: Inverse Captcha ---
 ///
 /// The night before Christmas, one of Santa's Elves calls you in a panic. "The printer's broken!
@@ -78,19 +80,10 @@ pub fn solve_part_two(captcha: &str) -> usize {
     solve_captcha(captcha, captcha.len() / 2)
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(3, solve_part_one("1122"));
-    assert_eq!(4, solve_part_one("1111"));
-    assert_eq!(0, solve_part_one("1234"));
-    assert_eq!(9, solve_part_one("91212129"));
-}
-
-#[test]
-fn examples_part_two() {
-    assert_eq!(6, solve_part_two("1212"));
-    assert_eq!(0, solve_part_two("1221"));
-    assert_eq!(4, solve_part_two("123425"));
-    assert_eq!(12, solve_part_two("123123"));
-    assert_eq!(4, solve_part_two("12131415"));
+/// A captcha of `size` random digits, rounded up to an even length as part two expects
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let len = (size + size % 2).max(2);
+    vec![(0..len)
+        .map(|_| char::from(b'0' + rng.below(10) as u8))
+        .collect()]
 }
@@ -1,3 +1,5 @@
+use rng::Rng;
+
 /// --- Day 2: Corruption Checksum ---
 ///
 /// As you walk through the door, a glowing humanoid shape yells in your direction. "You there! Your
@@ -93,12 +95,26 @@ pub fn solve_part_two(spreadsheet: &str) -> i32 {
     calculate_checksum(spreadsheet, row_checksum)
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(18, solve_part_one("5,1,9,5\n7,5,3\n2,4,6,8"))
-}
+/// A spreadsheet of `size` rows of 16 numbers. All but one number in a row are distinct primes and
+/// the odd one out is a small multiple of one of them, so every row has exactly one evenly
+/// divisible pair.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let primes: Vec<i32> = (1000..2000)
+        .filter(|n| (2..).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .collect();
+
+    (0..size.max(1))
+        .map(|_| {
+            let mut row = primes.clone();
+            rng.shuffle(&mut row);
+            row.truncate(15);
+            row.push(row[0] * rng.range(2, 9) as i32);
+            rng.shuffle(&mut row);
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(9, solve_part_two("5,9,2,8\n9,4,7,3\n3,8,6,5"))
+            row.iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        })
+        .collect()
 }
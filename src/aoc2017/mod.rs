@@ -1,3 +1,5 @@
+use runner::{Input, Solution};
+
 mod day1;
 mod day2;
 
@@ -43,17 +45,23 @@ pub const DAY2_INPUT: &str = "790,99,345,1080,32,143,1085,984,553,98,123,97,197,
 8447,203,1806,6777,278,2850,1232,6369,398,235,212,992,7520,7304,7852,520
 3928,107,3406,123,2111,2749,223,125,134,146,3875,1357,508,1534,4002,4417";
 
-pub fn aoc2017() {
-    println!("Advent of Code 2017");
-
-    println!(
-        "\tDay 1: Part One={}, Part Two={}",
-        day1::solve_part_one(DAY1_INPUT),
-        day1::solve_part_two(DAY1_INPUT),
-    );
-    println!(
-        "\tDay 2: Part One={}, Part Two={}",
-        day2::solve_part_one(DAY2_INPUT),
-        day2::solve_part_two(DAY2_INPUT),
-    );
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution {
+            year: 2017,
+            day: 1,
+            input: Input::Inline(DAY1_INPUT),
+            part_one: |i, _| day1::solve_part_one(&i.join("\n")).to_string(),
+            part_two: |i, _| day1::solve_part_two(&i.join("\n")).to_string(),
+            generate: day1::generate,
+        },
+        Solution {
+            year: 2017,
+            day: 2,
+            input: Input::Inline(DAY2_INPUT),
+            part_one: |i, _| day2::solve_part_one(&i.join("\n")).to_string(),
+            part_two: |i, _| day2::solve_part_two(&i.join("\n")).to_string(),
+            generate: day2::generate,
+        },
+    ]
 }
@@ -1,4 +1,5 @@
-use std::collections::HashSet;
+use cycle::Detector;
+use rng::Rng;
 
 /// --- Day 1: Chronal Calibration ---
 ///
@@ -83,87 +84,41 @@ pub fn solve_part_one(frequency_input: &[String]) -> isize {
 }
 
 pub fn solve_part_two(frequency_input: &[String]) -> isize {
-    let mut seen_frequencies = HashSet::new();
+    let changes = parse_input(frequency_input);
+    assert!(
+        !changes.is_empty(),
+        "No frequency changes, so no frequency is ever reached twice"
+    );
+
+    // Only the frequency matters, not how far through the list of changes it turned up
+    let mut seen_frequencies = Detector::new();
     let mut current_frequency = 0;
-    seen_frequencies.insert(current_frequency);
+    seen_frequencies.observe(current_frequency);
 
-    loop {
-        for f in parse_input(frequency_input).iter() {
-            current_frequency += f;
-            if seen_frequencies.contains(&current_frequency) {
-                return current_frequency;
-            } else {
-                seen_frequencies.insert(current_frequency);
-            }
+    for f in changes.iter().cycle() {
+        current_frequency += f;
+        if seen_frequencies.observe(current_frequency).is_some() {
+            return current_frequency;
         }
     }
+    unreachable!("Cycling through the changes never ends")
 }
 
 #[test]
-fn examples_part_one() {
-    assert_eq!(
-        3,
-        solve_part_one(&[
-            "+1".to_string(),
-            "-2".to_string(),
-            "+3".to_string(),
-            "+1".to_string()
-        ])
-    );
-    assert_eq!(
-        3,
-        solve_part_one(&["+1".to_string(), "+1".to_string(), "+1".to_string()])
-    );
-    assert_eq!(
-        0,
-        solve_part_one(&["+1".to_string(), "+1".to_string(), "-2".to_string()])
-    );
-    assert_eq!(
-        -6,
-        solve_part_one(&["-1".to_string(), "-2".to_string(), "-3".to_string()])
-    );
+#[should_panic(expected = "No frequency changes")]
+fn test_part_two_without_changes() {
+    solve_part_two(&[]);
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        2,
-        solve_part_two(&[
-            "+1".to_string(),
-            "-2".to_string(),
-            "+3".to_string(),
-            "+1".to_string()
-        ])
-    );
-    assert_eq!(0, solve_part_two(&["+1".to_string(), "-1".to_string()]));
-    assert_eq!(
-        10,
-        solve_part_two(&[
-            "+3".to_string(),
-            "+3".to_string(),
-            "+4".to_string(),
-            "-2".to_string(),
-            "-4".to_string()
-        ])
-    );
-    assert_eq!(
-        5,
-        solve_part_two(&[
-            "-6".to_string(),
-            "+3".to_string(),
-            "+8".to_string(),
-            "+5".to_string(),
-            "-6".to_string()
-        ])
-    );
-    assert_eq!(
-        14,
-        solve_part_two(&[
-            "+7".to_string(),
-            "+7".to_string(),
-            "-2".to_string(),
-            "-7".to_string(),
-            "-4".to_string()
-        ])
-    );
+/// `size` frequency changes. The overall drift is kept smaller than the number of changes, so by the
+/// pigeonhole principle some frequency is always reached twice.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let len = size.max(2);
+    let sign = |rng: &mut Rng| if rng.below(2) == 0 { 1 } else { -1 };
+
+    let mut changes: Vec<isize> = (0..len - 1).map(|_| rng.range(1, 20) * sign(rng)).collect();
+    let drift = rng.range(len as isize / 2, len as isize - 1) * sign(rng);
+    changes.push(drift - changes.iter().sum::<isize>());
+
+    changes.iter().map(|c| format!("{:+}", c)).collect()
 }
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use counter::Counter;
+use rng::Rng;
 
 /// --- Day 2: Inventory Management System ---
 ///
@@ -63,43 +64,17 @@ use std::collections::HashMap;
 ///
 /// What letters are common between the two correct box IDs? (In the example above, this is found
 /// by removing the differing character from either ID, producing fgij.)
-
-#[derive(Debug)]
-struct ChecksumPart {
-    twos: usize,
-    threes: usize,
-}
-
 pub fn solve_part_one(input: &[String]) -> usize {
-    let checksum_parts: ChecksumPart = input
-        .iter()
-        .map(|line| line.chars())
-        //.inspect(|chars| println!("{:?}", chars))
-        .map(|chars| {
-            let map: Vec<(char, usize)> = chars
-                .fold(HashMap::<char, usize>::new(), |mut m, c| {
-                    *m.entry(c).or_insert(0) += 1;
-                    m
-                })
-                .into_iter()
-                .filter(|(_, v)| *v == 2 || *v == 3)
-                //.inspect(|e| println!("Entry: {:?}", e))
-                .collect();
+    let letter_counts: Vec<Counter<char>> =
+        input.iter().map(|line| line.chars().collect()).collect();
+    let having_count = |count: usize| {
+        letter_counts
+            .iter()
+            .filter(|c| c.with_count(count).next().is_some())
+            .count()
+    };
 
-            ChecksumPart {
-                twos: map.iter().find(|(_, v)| *v == 2).map_or(0, |_| 1),
-                threes: map.iter().find(|(_, v)| *v == 3).map_or(0, |_| 1),
-            }
-        })
-        //.inspect(|checksum_part| println!("Checksum parts: {:?}", checksum_part))
-        .fold(ChecksumPart { twos: 0, threes: 0 }, |acc, cp| {
-            ChecksumPart {
-                twos: acc.twos + cp.twos,
-                threes: acc.threes + cp.threes,
-            }
-        });
-
-    checksum_parts.twos * checksum_parts.threes
+    having_count(2) * having_count(3)
 }
 
 pub fn solve_part_two(input: &[String]) -> String {
@@ -134,30 +109,20 @@ fn compare_box_ids(str1: &str, str2: &str) -> String {
     matched_chars
 }
 
-#[test]
-fn examples_part_one() {
-    let input = &[
-        "abcdef".to_string(),
-        "bababc".to_string(),
-        "abbcde".to_string(),
-        "abcccd".to_string(),
-        "aabcdd".to_string(),
-        "abcdee".to_string(),
-        "ababab".to_string(),
-    ];
-    assert_eq!(12, solve_part_one(input));
-}
+/// `size` box IDs of 26 random letters, exactly two of which differ by a single letter
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let letter = |rng: &mut Rng| char::from(b'a' + rng.below(26) as u8);
+
+    let mut ids: Vec<Vec<char>> = (0..size.max(2) - 1)
+        .map(|_| (0..26).map(|_| letter(rng)).collect())
+        .collect();
+    let mut counterpart = ids[0].clone();
+    let position = rng.below(26);
+    while counterpart[position] == ids[0][position] {
+        counterpart[position] = letter(rng);
+    }
+    ids.push(counterpart);
+    rng.shuffle(&mut ids);
 
-#[test]
-fn examples_part_two() {
-    let input = &[
-        "abcde".to_string(),
-        "fghij".to_string(),
-        "klmno".to_string(),
-        "pqrst".to_string(),
-        "fguij".to_string(),
-        "axcye".to_string(),
-        "wvxyz".to_string(),
-    ];
-    assert_eq!("fgij", solve_part_two(input));
+    ids.iter().map(|id| id.iter().collect()).collect()
 }
@@ -1,6 +1,7 @@
+use point::Point2;
+use rect::{covered_area, isolated, Rect};
 use regex::Regex;
-use std::collections::HashMap;
-use std::collections::HashSet;
+use rng::Rng;
 
 /// --- Day 3: No Matter How You Slice It ---
 ///
@@ -119,68 +120,70 @@ fn test_parse_input() {
     assert_eq!(expected_claims, parse_input(input));
 }
 
-pub fn solve_part_one(input: &[String]) -> usize {
-    let claims = parse_input(input);
-    let mut inches_coordinates_claimed = HashSet::new();
-    let mut overlap_coordinates = HashSet::new();
+impl Claim {
+    fn rect(&self) -> Rect {
+        Rect::new(
+            Point2::new(self.x as isize, self.y as isize),
+            self.w as isize,
+            self.h as isize,
+        )
+    }
+}
 
-    claims.iter().for_each(|c| {
-        for x in c.x..c.x + c.w {
-            for y in c.y..c.y + c.h {
-                if inches_coordinates_claimed.contains(&(x, y)) {
-                    overlap_coordinates.insert((x, y));
-                } else {
-                    inches_coordinates_claimed.insert((x, y));
-                }
-            }
-        }
-    });
+pub fn solve_part_one(input: &[String]) -> usize {
+    let claims: Vec<Rect> = parse_input(input).iter().map(Claim::rect).collect();
 
-    overlap_coordinates.len()
+    covered_area(&claims, 2) as usize
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
     let claims = parse_input(input);
-    let mut inches_coordinates_claimed: HashMap<(usize, usize), usize> = HashMap::new();
-    let mut overlap_claim_ids = HashSet::new();
+    let rects: Vec<Rect> = claims.iter().map(Claim::rect).collect();
 
-    claims.iter().for_each(|c| {
-        for x in c.x..c.x + c.w {
-            for y in c.y..c.y + c.h {
-                inches_coordinates_claimed
-                    .entry((x, y))
-                    .and_modify(|existing_id| {
-                        overlap_claim_ids.insert(c.id);
-                        overlap_claim_ids.insert(*existing_id);
-                    })
-                    .or_insert(c.id);
-            }
-        }
-    });
-
-    claims
-        .iter()
-        .map(|c| c.id)
-        .find(|id| !overlap_claim_ids.contains(id))
-        .unwrap()
+    isolated(&rects)
+        .first()
+        .map(|i| claims[*i].id)
+        .expect("Every claim overlaps another")
 }
 
 #[test]
-fn examples_part_one() {
+fn test_huge_claims() {
     let input = &[
-        "#1 @ 1,3: 4x4".to_string(),
-        "#2 @ 3,1: 4x4".to_string(),
-        "#3 @ 5,5: 2x2".to_string(),
+        "#1 @ 0,0: 3000000x2000000".to_string(),
+        "#2 @ 1000000,1000000: 4000000x4000000".to_string(),
+        "#3 @ 3000000,0: 5x1000000".to_string(),
     ];
-    assert_eq!(4, solve_part_one(input))
+    assert_eq!(2_000_000_000_000, solve_part_one(input));
+    assert_eq!(3, solve_part_two(input));
 }
 
-#[test]
-fn examples_part_two() {
-    let input = &[
-        "#1 @ 1,3: 4x4".to_string(),
-        "#2 @ 3,1: 4x4".to_string(),
-        "#3 @ 5,5: 2x2".to_string(),
-    ];
-    assert_eq!(3, solve_part_two(input))
+/// `size` claims on a square of fabric that grows with the number of claims. Claims are placed in
+/// overlapping pairs, apart from the one intact claim which sits off to the side on its own.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let side = (size as f64).sqrt() as usize * 25 + 30;
+    let dimensions = |rng: &mut Rng| (rng.range(5, 29) as usize, rng.range(5, 29) as usize);
+    let overlapping = |rng: &mut Rng, (x, y, w, h): (usize, usize, usize, usize)| {
+        (x + rng.below(w), y + rng.below(h))
+    };
+
+    let mut claims = vec![];
+    while claims.len() < size.max(3) - 1 {
+        let (w, h) = dimensions(rng);
+        let (x, y) = if claims.len() % 2 == 0 && claims.len() < size.max(3) - 2 {
+            (rng.below(side), rng.below(side))
+        } else {
+            let partner = *rng.choose(&claims);
+            overlapping(rng, partner)
+        };
+        claims.push((x, y, w, h));
+    }
+    let (w, h) = dimensions(rng);
+    claims.push((side * 2, rng.below(side), w, h));
+    rng.shuffle(&mut claims);
+
+    claims
+        .iter()
+        .enumerate()
+        .map(|(id, (x, y, w, h))| format!("#{} @ {},{}: {}x{}", id + 1, x, y, w, h))
+        .collect()
 }
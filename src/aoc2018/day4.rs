@@ -1,4 +1,6 @@
+use chrono::{Duration, NaiveDate};
 use regex::Regex;
+use rng::Rng;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
@@ -419,3 +421,39 @@ fn test_part_two() {
 
     assert_eq!(4455, solve_part_two(input))
 }
+
+/// Shuffled guard logs for `size` nights, up to a year's worth. Shifts begin just before or just
+/// after midnight, and whoever is on duty dozes off at least once during the midnight hour.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let guards: Vec<usize> = (0..(size / 5).clamp(1, 40))
+        .map(|_| rng.range(10, 3499) as usize)
+        .collect();
+    let first_night = NaiveDate::from_ymd(1518, 1, 2);
+    let timestamp = |date: NaiveDate, hour: usize, minute: usize| {
+        format!("[{} {:02}:{:02}]", date.format("%Y-%m-%d"), hour, minute)
+    };
+
+    let mut logs = vec![];
+    for night in 0..size.clamp(1, 364) {
+        let date = first_night + Duration::days(night as i64);
+        let guard = rng.choose(&guards);
+        let shift_start = if rng.below(2) == 0 {
+            timestamp(date.pred(), 23, rng.range(45, 59) as usize)
+        } else {
+            timestamp(date, 0, rng.below(4))
+        };
+        logs.push(format!("{} Guard #{} begins shift", shift_start, guard));
+
+        let mut minutes: Vec<usize> = (5..60).collect();
+        rng.shuffle(&mut minutes);
+        minutes.truncate(2 * rng.range(1, 3) as usize);
+        minutes.sort_unstable();
+        for naps in minutes.chunks(2) {
+            logs.push(format!("{} falls asleep", timestamp(date, 0, naps[0])));
+            logs.push(format!("{} wakes up", timestamp(date, 0, naps[1])));
+        }
+    }
+    rng.shuffle(&mut logs);
+
+    logs
+}
@@ -1,3 +1,5 @@
+use rng::Rng;
+
 ///--- Day 5: Alchemical Reduction ---
 ///
 /// You've managed to sneak in to the prototype suit manufacturing lab. The Elves are making decent
@@ -113,17 +115,29 @@ fn test_react() {
     assert_eq!("", react("abBA"));
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(10, solve_part_one(&["dabAcCaCBAcCcaDA".to_string()]));
-
-    assert_eq!(0, solve_part_one(&["aA".to_string()]));
-    assert_eq!(0, solve_part_one(&["abBA".to_string()]));
-    assert_eq!(4, solve_part_one(&["abAB".to_string()]));
-    assert_eq!(6, solve_part_one(&["aabAAB".to_string()]));
-}
+/// A polymer of `size` units. Each unit has a fair chance of reacting with the one before it, so
+/// there's plenty for the reaction to chew through.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut polymer: Vec<char> = vec![];
+    for _ in 0..size.max(1) {
+        let unit = match polymer.last() {
+            Some(previous) if rng.below(3) == 0 => {
+                if previous.is_lowercase() {
+                    previous.to_ascii_uppercase()
+                } else {
+                    previous.to_ascii_lowercase()
+                }
+            }
+            _ => {
+                let letter = char::from(b'a' + rng.below(26) as u8);
+                match rng.below(2) {
+                    0 => letter,
+                    _ => letter.to_ascii_uppercase(),
+                }
+            }
+        };
+        polymer.push(unit);
+    }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(4, solve_part_two(&["dabAcCaCBAcCcaDA".to_string()]));
+    vec![polymer.iter().collect()]
 }
@@ -1,4 +1,7 @@
+use grid::Grid;
+use point::Point2;
 use regex::Regex;
+use rng::Rng;
 
 /// --- Day 6: Chronal Coordinates ---
 ///
@@ -109,98 +112,97 @@ use regex::Regex;
 /// What is the size of the region containing all locations which have a total distance to all given
 /// coordinates of less than 10000?
 
-#[derive(Debug)]
-struct Coord {
-    id: Option<usize>,
-    x: isize,
-    y: isize,
-}
-
 #[derive(Debug)]
 struct CoordSize {
-    id: usize,
     area: isize,
     infinite: bool,
 }
 
-fn parse_input(input: &[String]) -> Vec<Coord> {
+/// The coords in the order given, so each one's ID is its index
+fn parse_input(input: &[String]) -> Vec<Point2> {
     let re = Regex::new(r"([0-9]+), *([0-9]+)").unwrap();
 
     input
         .iter()
         //        .inspect(|l| println!("To parse: {}", l))
         .map(|l| re.captures(l).unwrap())
-        .enumerate()
-        .map(|(id, c)| Coord {
-            id: Some(id),
-            x: c.get(1).map_or(0, |m| m.as_str().parse().unwrap()),
-            y: c.get(2).map_or(0, |m| m.as_str().parse().unwrap()),
+        .map(|c| {
+            Point2::new(
+                c.get(1).map_or(0, |m| m.as_str().parse().unwrap()),
+                c.get(2).map_or(0, |m| m.as_str().parse().unwrap()),
+            )
         })
         // .inspect(|c| println!("Parsed coord: {:?}", c))
         .collect()
 }
 
-pub fn solve_part_one(input: &[String]) -> isize {
-    let coords = parse_input(input);
-
+/// A grid covering the bounding box of the coords, with each cell worked out from its position
+fn bounding_grid<T, F>(coords: &[Point2], mut cell: F) -> Grid<T>
+where
+    F: FnMut(Point2) -> T,
+{
     let min_x = coords.iter().map(|c| c.x).min().unwrap();
     let max_x = coords.iter().map(|c| c.x).max().unwrap();
     let min_y = coords.iter().map(|c| c.y).min().unwrap();
     let max_y = coords.iter().map(|c| c.y).max().unwrap();
     //    println!("Bounds: {},{} - {},{}", min_x, min_y, max_x, max_y);
 
+    Grid::from_fn(
+        (max_x - min_x + 1) as usize,
+        (max_y - min_y + 1) as usize,
+        |gx, gy| cell(Point2::new(min_x + gx as isize, min_y + gy as isize)),
+    )
+}
+
+/// The ID of the coord closest to a cell, if there's only one that close
+fn closest_coord(coords: &[Point2], cell: Point2) -> Option<usize> {
+    // Find distance to each coord (x-delta + y-delta)
+    let coord_distances: Vec<(usize, isize)> = coords
+        .iter()
+        .enumerate()
+        .map(|(id, coord)| (id, coord.manhattan(cell)))
+        .collect();
+
+    // Find min distance
+    let min_dist = coord_distances.iter().min_by_key(|cd| cd.1).unwrap();
+    if coord_distances
+        .iter()
+        .filter(|(_, cd)| cd == &min_dist.1)
+        .count()
+        > 1
+    {
+        // If multiple coords have the same distance it belongs to no coord
+        None
+    } else {
+        Some(min_dist.0)
+    }
+}
+
+pub fn solve_part_one(input: &[String]) -> isize {
+    let coords = parse_input(input);
+    let closest = bounding_grid(&coords, |cell| closest_coord(&coords, cell));
+
     let mut coord_areas: Vec<CoordSize> = coords
         .iter()
-        .map(|c| CoordSize {
-            id: c.id.unwrap(),
+        .map(|_| CoordSize {
             area: 0,
             infinite: false,
         })
         .collect();
-    for gx in min_x..=max_x {
-        for gy in min_y..=max_y {
-            // Find distance to each coord (x-delta + y-delta)
-            let coord_distances: Vec<(Option<usize>, isize)> = coords
-                .iter()
-                .map(|coord| {
-                    (
-                        coord.id,
-                        manhattan_distance(
-                            coord,
-                            &Coord {
-                                id: None,
-                                x: gx,
-                                y: gy,
-                            },
-                        ),
-                    )
-                })
-                //                .inspect(|(cid, dist)| println!("{}, {} : {:?} - {}", gx, gy, cid, dist))
-                .collect();
-
-            // Find min distance
-            let min_dist = coord_distances.iter().min_by_key(|cd| cd.1).unwrap();
-            //            println!("Min dist: {},{} -> {:?}", gx, gy, min_dist);
-            if coord_distances
-                .iter()
-                .filter(|(_, cd)| cd == &min_dist.1)
-                .count()
-                > 1
-            {
-                // If multiple coords have the same distance it belongs to no coord
-            } else {
-                let coord_area = coord_areas
-                    .iter_mut()
-                    .find(|ca| ca.id == min_dist.0.unwrap())
-                    .unwrap();
-                // if cell is on boundary, mark is as extending to infinite for the coord_areas[coord_id]
-                if gx == min_x || gx == max_x || gy == min_y || gy == max_y {
-                    coord_area.infinite = true;
-                    coord_area.area = -1;
-                } else if !coord_area.infinite {
-                    coord_area.area += 1;
-                }
-            }
+    // If a cell on the boundary is closest to a coord, its area extends out to infinity
+    let boundary = closest
+        .row(0)
+        .iter()
+        .chain(closest.row(closest.height() - 1))
+        .chain(closest.column(0))
+        .chain(closest.column(closest.width() - 1));
+    for id in boundary.flatten() {
+        coord_areas[*id].infinite = true;
+        coord_areas[*id].area = -1;
+    }
+    for id in closest.iter().flatten() {
+        if !coord_areas[*id].infinite {
+            coord_areas[*id].area += 1;
         }
     }
 
@@ -208,54 +210,27 @@ pub fn solve_part_one(input: &[String]) -> isize {
     coord_areas.iter().max_by_key(|ca| ca.area).unwrap().area
 }
 
-fn manhattan_distance(c1: &Coord, c2: &Coord) -> isize {
-    (c1.x - c2.x).abs() + (c1.y - c2.y).abs()
-}
-
-pub fn solve_part_two(input: &[String]) -> isize {
+fn safe_region_size(input: &[String], max_total_distance: isize) -> isize {
     let coords = parse_input(input);
+    let total_distances = bounding_grid(&coords, |cell| {
+        coords
+            .iter()
+            .map(|coord| coord.manhattan(cell))
+            .sum::<isize>()
+    });
 
-    let min_x = coords.iter().map(|c| c.x).min().unwrap();
-    let max_x = coords.iter().map(|c| c.x).max().unwrap();
-    let min_y = coords.iter().map(|c| c.y).min().unwrap();
-    let max_y = coords.iter().map(|c| c.y).max().unwrap();
-    //    println!("Bounds: {},{} - {},{}", min_x, min_y, max_x, max_y);
-
-    let mut safe_area = 0;
-
-    for gx in min_x..=max_x {
-        for gy in min_y..=max_y {
-            // Find distance to each coord (x-delta + y-delta)
-            let coord_distances: Vec<(Option<usize>, isize)> = coords
-                .iter()
-                .map(|coord| {
-                    (
-                        coord.id,
-                        manhattan_distance(
-                            coord,
-                            &Coord {
-                                id: None,
-                                x: gx,
-                                y: gy,
-                            },
-                        ),
-                    )
-                })
-                //                .inspect(|(cid, dist)| println!("{}, {} : {:?} - {}", gx, gy, cid, dist))
-                .collect();
-
-            let dist_sum: isize = coord_distances.iter().map(|(_, cd)| cd).sum();
-            if dist_sum < 10000 {
-                safe_area += 1;
-            }
-        }
-    }
+    total_distances
+        .iter()
+        .filter(|dist_sum| **dist_sum < max_total_distance)
+        .count() as isize
+}
 
-    safe_area
+pub fn solve_part_two(input: &[String]) -> isize {
+    safe_region_size(input, 10000)
 }
 
 #[test]
-fn examples_part_one() {
+fn examples_part_two() {
     let input = &[
         "1, 1".to_string(),
         "1, 6".to_string(),
@@ -265,8 +240,23 @@ fn examples_part_one() {
         "8, 9".to_string(),
     ];
 
-    assert_eq!(17, solve_part_one(input));
+    assert_eq!(16, safe_region_size(input, 32));
 }
 
-#[test]
-fn examples_part_two() {}
+/// `size` distinct coordinates spread over an area that grows with their number, about as densely
+/// packed as the real puzzle input
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let side = (size as f64).sqrt() as isize * 40 + 40;
+    let mut coords = vec![];
+    while coords.len() < size.max(1) {
+        let coord = (rng.range(40, 40 + side), rng.range(40, 40 + side));
+        if !coords.contains(&coord) {
+            coords.push(coord);
+        }
+    }
+
+    coords
+        .iter()
+        .map(|(x, y)| format!("{}, {}", x, y))
+        .collect()
+}
@@ -1,8 +1,28 @@
-//use std::collections::HashMap;
+use graph::Graph;
+use lazy_static::lazy_static;
+use regex::Regex;
+use rng::Rng;
 
-//use regex::Regex;
-//use std::collections::BTreeSet;
-//use std::collections::HashSet;
+lazy_static! {
+    static ref RE_INSTRUCTION: Regex = Regex::new(
+        r"^Step (?P<before>\w+) must be finished before step (?P<after>\w+) can begin.$"
+    )
+    .unwrap();
+}
+
+/// Steps pointing at the steps that have to wait for them
+fn parse_steps(input: &[String]) -> Graph {
+    let mut steps = Graph::new();
+    input.iter().for_each(|l| {
+        let matched = RE_INSTRUCTION.captures(l).unwrap();
+        steps.add_edge(
+            matched.name("before").unwrap().as_str(),
+            matched.name("after").unwrap().as_str(),
+            (),
+        );
+    });
+    steps
+}
 
 /// --- Day 7: The Sum of Its Parts ---
 ///
@@ -61,130 +81,46 @@
 /// So, in this example, the correct order is CABDFE.
 ///
 /// In what order should the steps in your instructions be completed?
+pub fn solve_part_one(input: &[String]) -> String {
+    let steps = parse_steps(input);
 
-//#[derive(Debug)]
-//struct Node {
-//    id: String,
-//    finishes_before: Vec<String>,
-//    prerequisites: Vec<String>,
-//}
-//
-//#[derive(Debug)]
-//struct Instruction {
-//    prerequisite: String,
-//    node_id: String,
-//}
-//
-//fn parse_input(input: &[String]) -> Vec<Instruction> {
-//    let re = Regex::new(r"Step ([A-Z]) must be finished before step ([A-Z]) can begin.").unwrap();
-//
-//    input
-//        .iter()
-//        //        .inspect(|l| println!("To parse: {}", l))
-//        .map(|l| re.captures(l).unwrap())
-//        .map(|c| Instruction {
-//            prerequisite: c.get(1).map_or("???".to_owned(), |m| m.as_str().to_owned()),
-//            node_id: c.get(2).map_or("???".to_owned(), |m| m.as_str().to_owned()),
-//        })
-//        .inspect(|i| println!("Parsed Instruction: {:?}", i))
-//        .collect()
-//}
-
-pub fn solve_part_one(_input: &[String]) -> String {
-    //    let instructions = parse_input(input);
-    //
-    //    let mut graph: HashMap<String, Node> = HashMap::new();
-    //    instructions.iter().for_each(|i| {
-    //        graph
-    //            .entry(i.prerequisite.clone())
-    //            .or_insert(Node {
-    //                id: i.prerequisite.clone(),
-    //                finishes_before: Vec::new(),
-    //                prerequisites: Vec::new(),
-    //            })
-    //            .finishes_before
-    //            .push(i.node_id.clone());
-    //        graph
-    //            .entry(i.node_id.clone())
-    //            .or_insert(Node {
-    //                id: i.node_id.clone(),
-    //                finishes_before: Vec::new(),
-    //                prerequisites: Vec::new(),
-    //            })
-    //            .prerequisites
-    //            .push(i.prerequisite.clone());
-    //    });
-    //
-    //    // Find start node, one with no pre-requesites
-    //    let mut working_node = graph
-    //        .iter()
-    //        .find_map(|(_, v)| {
-    //            if v.prerequisites.is_empty() {
-    //                Some(v)
-    //            } else {
-    //                None
-    //            }
-    //        })
-    //        .unwrap();
-    //
-    //    let mut output = String::new();
-
-    //    let mut options = Vec::new();
-    //
-    //    loop {
-    //        println!("Working node: {:?}", working_node);
-    //
-    //        output += working_node.id.clone().as_str();
-    //
-    //        working_node.finishes_before.iter().for_each(|p| {
-    //            if !options.contains(p) {
-    //                options.push(p.to_string());
-    //            }
-    //        });
-    //
-    //        println!("Options: {:?}", options);
-    //
-    //        options.sort();
-    //
-    //        match options.iter().filter(|o| graph.get(&o).unwrap().prerequisites.is_empty()).next() {
-    //            Some(o) => {
-    //                working_node = graph.get(&o).unwrap();
-    //            }
-    //            None => { break; }
-    //        }
-    //
-    ////        options.reverse();
-    ////        match options.pop() {
-    ////            Some(o) => {
-    ////                working_node = graph.get(&o).unwrap();
-    ////            }
-    ////            None => { break; }
-    ////        }
-    //    }
-
-    //    output
-
-    "TODO".to_string()
+    steps
+        .topological_sort_by_key(|id| steps.name(id).to_string())
+        .expect("Steps shouldn't wait on themselves")
+        .iter()
+        .map(|id| steps.name(*id))
+        .collect()
 }
 
 pub fn solve_part_two(_input: &[String]) -> String {
     "TODO".to_string()
 }
 
-#[test]
-fn examples_part_one() {
-    // let input = &[
-    //     "Step C must be finished before step A can begin.".to_owned(),
-    //     "Step C must be finished before step F can begin.".to_owned(),
-    //     "Step A must be finished before step B can begin.".to_owned(),
-    //     "Step A must be finished before step D can begin.".to_owned(),
-    //     "Step B must be finished before step E can begin.".to_owned(),
-    //     "Step D must be finished before step E can begin.".to_owned(),
-    //     "Step F must be finished before step E can begin.".to_owned(),
-    // ];
-    //
-    // assert_eq!("CABDFE", solve_part_one(input));
-}
+/// Instructions for `size` steps (up to the 26 the alphabet allows). Every step but the first
+/// waits on at least one step before it in a hidden order, plus a few extra random dependencies.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut steps: Vec<char> = (b'A'..=b'Z').map(char::from).collect();
+    rng.shuffle(&mut steps);
+    steps.truncate(size.clamp(2, 26));
 
-#[test]
-fn examples_part_two() {}
+    let mut dependencies = vec![];
+    for (i, step) in steps.iter().enumerate().skip(1) {
+        for _ in 0..rng.range(1, 3) {
+            let dependency = (steps[rng.below(i)], *step);
+            if !dependencies.contains(&dependency) {
+                dependencies.push(dependency);
+            }
+        }
+    }
+    rng.shuffle(&mut dependencies);
+
+    dependencies
+        .iter()
+        .map(|(before, after)| {
+            format!(
+                "Step {} must be finished before step {} can begin.",
+                before, after
+            )
+        })
+        .collect()
+}
@@ -1,4 +1,4 @@
-use util;
+use runner::{Input, Solution};
 
 mod day1;
 mod day2;
@@ -8,48 +8,63 @@ mod day5;
 mod day6;
 mod day7;
 
-pub fn aoc2018() {
-    println!("Advent of Code 2018");
-
-    println!(
-        "\tDay 1: Part One={}, Part Two={}",
-        day1::solve_part_one(&util::read_file_input("resources/2018/day1.txt")),
-        day1::solve_part_two(&util::read_file_input("resources/2018/day1.txt")),
-    );
-
-    println!(
-        "\tDay 2: Part One={}, Part Two={}",
-        day2::solve_part_one(&util::read_file_input("resources/2018/day2.txt")),
-        day2::solve_part_two(&util::read_file_input("resources/2018/day2.txt")),
-    );
-
-    println!(
-        "\tDay 3: Part One={}, Part Two={}",
-        day3::solve_part_one(&util::read_file_input("resources/2018/day3.txt")),
-        day3::solve_part_two(&util::read_file_input("resources/2018/day3.txt")),
-    );
-
-    println!(
-        "\tDay 4: Part One={}, Part Two={}",
-        day4::solve_part_one(&util::read_file_input("resources/2018/day4.txt")),
-        day4::solve_part_two(&util::read_file_input("resources/2018/day4.txt")),
-    );
-
-    println!(
-        "\tDay 5: Part One={}, Part Two={}",
-        day5::solve_part_one(&util::read_file_input("resources/2018/day5.txt")),
-        day5::solve_part_two(&util::read_file_input("resources/2018/day5.txt")),
-    );
-
-    println!(
-        "\tDay 6: Part One={}, Part Two={}",
-        day6::solve_part_one(&util::read_file_input("resources/2018/day6.txt")),
-        day6::solve_part_two(&util::read_file_input("resources/2018/day6.txt")),
-    );
-
-    println!(
-        "\tDay 7: Part One={}, Part Two={}",
-        day7::solve_part_one(&util::read_file_input("resources/2018/day7.txt")),
-        day7::solve_part_two(&util::read_file_input("resources/2018/day7.txt")),
-    );
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution {
+            year: 2018,
+            day: 1,
+            input: Input::Lines("resources/2018/day1.txt"),
+            part_one: |i, _| day1::solve_part_one(i).to_string(),
+            part_two: |i, _| day1::solve_part_two(i).to_string(),
+            generate: day1::generate,
+        },
+        Solution {
+            year: 2018,
+            day: 2,
+            input: Input::Lines("resources/2018/day2.txt"),
+            part_one: |i, _| day2::solve_part_one(i).to_string(),
+            part_two: |i, _| day2::solve_part_two(i).to_string(),
+            generate: day2::generate,
+        },
+        Solution {
+            year: 2018,
+            day: 3,
+            input: Input::Lines("resources/2018/day3.txt"),
+            part_one: |i, _| day3::solve_part_one(i).to_string(),
+            part_two: |i, _| day3::solve_part_two(i).to_string(),
+            generate: day3::generate,
+        },
+        Solution {
+            year: 2018,
+            day: 4,
+            input: Input::Lines("resources/2018/day4.txt"),
+            part_one: |i, _| day4::solve_part_one(i).to_string(),
+            part_two: |i, _| day4::solve_part_two(i).to_string(),
+            generate: day4::generate,
+        },
+        Solution {
+            year: 2018,
+            day: 5,
+            input: Input::Lines("resources/2018/day5.txt"),
+            part_one: |i, _| day5::solve_part_one(i).to_string(),
+            part_two: |i, _| day5::solve_part_two(i).to_string(),
+            generate: day5::generate,
+        },
+        Solution {
+            year: 2018,
+            day: 6,
+            input: Input::Lines("resources/2018/day6.txt"),
+            part_one: |i, _| day6::solve_part_one(i).to_string(),
+            part_two: |i, _| day6::solve_part_two(i).to_string(),
+            generate: day6::generate,
+        },
+        Solution {
+            year: 2018,
+            day: 7,
+            input: Input::Lines("resources/2018/day7.txt"),
+            part_one: |i, _| day7::solve_part_one(i).to_string(),
+            part_two: |i, _| day7::solve_part_two(i).to_string(),
+            generate: day7::generate,
+        },
+    ]
 }
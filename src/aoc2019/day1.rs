@@ -1,3 +1,5 @@
+use rng::Rng;
+
 /// --- Day 1: The Tyranny of the Rocket Equation ---
 ///
 /// Santa has become stranded at the edge of the Solar System while delivering presents to other
@@ -98,17 +100,9 @@ pub fn solve_part_two(input: &[String]) -> isize {
         .sum()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(2, solve_part_one(&["12".to_string(),]));
-    assert_eq!(2, solve_part_one(&["14".to_string(),]));
-    assert_eq!(654, solve_part_one(&["1969".to_string(),]));
-    assert_eq!(33583, solve_part_one(&["100756".to_string(),]));
-}
-
-#[test]
-fn examples_part_two() {
-    assert_eq!(2, solve_part_two(&["14".to_string(),]));
-    assert_eq!(966, solve_part_two(&["1969".to_string(),]));
-    assert_eq!(50346, solve_part_two(&["100756".to_string(),]));
+/// `size` module masses in the same range as the real puzzle input
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size.max(1))
+        .map(|_| rng.range(50_000, 150_000).to_string())
+        .collect()
 }
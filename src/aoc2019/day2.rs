@@ -1,4 +1,5 @@
-use util::parse_int_csv;
+use parse::{comma_separated, parse_line, signed};
+use rng::Rng;
 
 pub fn part_1_mangling(input_codes: &mut [isize]) {
     input_codes[1] = 12;
@@ -9,7 +10,7 @@ pub fn solve_part_one<F>(input: &[String], mangle: F) -> isize
 where
     F: Fn(&mut [isize]),
 {
-    let mut int_codes = parse_int_csv(input.first().unwrap());
+    let mut int_codes = parse_line(input.first().unwrap(), comma_separated(signed)).unwrap();
 
     run_vm(&mut int_codes, mangle)
 }
@@ -99,5 +100,25 @@ fn examples_part_one() {
     );
 }
 
-#[test]
-fn examples_part_two() {}
+/// An intcode program `size` values long (at least 100, as nouns and verbs address the first 100
+/// values) that adds together the values at its noun and verb. Exactly one noun and verb pair
+/// address two planted values that add up to part two's target output.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut program: Vec<isize> = vec![1, 0, 0, 0, 99];
+    // Filler is too small for any pair of it, or it and a planted value, to hit the target
+    program.extend((5..size.max(100)).map(|_| rng.range(0, 999_999)));
+
+    let noun = rng.range(5, 99) as usize;
+    let mut verb = rng.range(5, 99) as usize;
+    while verb == noun {
+        verb = rng.range(5, 99) as usize;
+    }
+    program[noun] = rng.range(1_000_000, 18_690_720);
+    program[verb] = 19_690_720 - program[noun];
+
+    vec![program
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(",")]
+}
@@ -1,23 +1,22 @@
-use std::collections::HashSet;
-use std::hash::{Hash, Hasher};
+use geometry::Polyline;
+use point::{Direction, Point2};
+use rng::Rng;
 
 #[derive(Debug)]
-enum Move {
-    Left(isize),
-    Right(isize),
-    Up(isize),
-    Down(isize),
+struct Move {
+    direction: Direction,
+    distance: isize,
 }
 
 impl Move {
     pub fn parse(input: &str) -> Move {
         let distance: isize = input[1..].parse().unwrap();
-        match &input[0..1] {
-            "L" => Move::Left(distance),
-            "R" => Move::Right(distance),
-            "U" => Move::Up(distance),
-            "D" => Move::Down(distance),
-            _ => panic!("Invalid move: {}", input),
+        match input.chars().next().and_then(Direction::from_char) {
+            Some(direction) => Move {
+                direction,
+                distance,
+            },
+            None => panic!("Invalid move: {}", input),
         }
     }
 }
@@ -26,84 +25,36 @@ fn parse_wire(input: &str) -> Vec<Move> {
     input.split(',').map(Move::parse).collect()
 }
 
-#[derive(Debug)]
-struct WireCoord {
-    x: isize,
-    y: isize,
-    length: isize,
-}
-impl Hash for WireCoord {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
-    }
-}
-impl PartialEq for WireCoord {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
+/// Lay the wire out from the central port as a line of straight runs
+fn realise_wire(wire: Vec<Move>) -> Polyline {
+    Polyline::from_moves(
+        Point2::ORIGIN,
+        wire.iter().map(|m| m.direction.vector() * m.distance),
+    )
 }
 
-impl Eq for WireCoord {}
-
-fn realise_wire(wire: Vec<Move>) -> HashSet<WireCoord> {
-    let mut coords = HashSet::new();
-
-    let mut x: isize = 0;
-    let mut y: isize = 0;
-    let mut length: isize = 0;
-
-    for wire_move in wire {
-        let mut dx: isize = 0;
-        let mut dy: isize = 0;
-        let dist = match wire_move {
-            Move::Left(distance) => {
-                dx = -1;
-                distance
-            }
-            Move::Right(distance) => {
-                dx = 1;
-                distance
-            }
-            Move::Up(distance) => {
-                dy = 1;
-                distance
-            }
-            Move::Down(distance) => {
-                dy = -1;
-                distance
-            }
-        };
-
-        for _i in 0..dist {
-            x += dx;
-            y += dy;
-            length += 1;
-
-            coords.insert(WireCoord { x, y, length });
-        }
-    }
-
-    coords
-}
-
-fn find_minimum_manhattan_distance_intersection(
-    wire1: HashSet<WireCoord>,
-    wire2: HashSet<WireCoord>,
-) -> isize {
+/// Everywhere the wires cross, with the length of each wire it took to get there, ignoring the
+/// central port they both start at
+fn crossings(wire1: &Polyline, wire2: &Polyline) -> Vec<(Point2, isize, isize)> {
     wire1
+        .intersections(wire2)
         .into_iter()
-        .filter(|c| wire2.contains(c))
-        .map(|wc| wc.x.abs() + wc.y.abs())
+        .filter(|(c, _, _)| *c != Point2::ORIGIN)
+        .collect()
+}
+
+fn find_minimum_manhattan_distance_intersection(wire1: Polyline, wire2: Polyline) -> isize {
+    crossings(&wire1, &wire2)
+        .iter()
+        .map(|(c, _, _)| c.manhattan(Point2::ORIGIN))
         .min()
         .unwrap()
 }
 
-fn find_minimum_length_intersection(wire1: HashSet<WireCoord>, wire2: HashSet<WireCoord>) -> isize {
-    wire1
-        .into_iter()
-        .filter(|c| wire2.contains(c))
-        .map(|w1c| w1c.length + wire2.get(&w1c).unwrap().length)
+fn find_minimum_length_intersection(wire1: Polyline, wire2: Polyline) -> isize {
+    crossings(&wire1, &wire2)
+        .iter()
+        .map(|(_, w1_length, w2_length)| w1_length + w2_length)
         .min()
         .unwrap()
 }
@@ -124,38 +75,25 @@ pub fn solve_part_two(input: &[String]) -> isize {
 
     let wire1 = realise_wire(wire1);
     let wire2 = realise_wire(wire2);
-    // println!("wire1 coords: {:?}", wire1);
-    // println!("wire2 coords: {:?}", wire2);
 
     find_minimum_length_intersection(wire1, wire2)
 }
 
-#[test]
-fn examples_part_one() {
-    let input = &[
-        "R75,D30,R83,U83,L12,D49,R71,U7,L72".to_string(),
-        "U62,R66,U55,R34,D71,R55,D58,R83".to_string(),
-    ];
-    assert_eq!(159, solve_part_one(input));
-
-    let input = &[
-        "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51".to_string(),
-        "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7".to_string(),
-    ];
-    assert_eq!(135, solve_part_one(input));
-}
+/// Two wires of `size` random moves each. The wires start off heading right then up and up then
+/// right respectively, and are sized so that they're bound to cross.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let (across, up) = (rng.range(1, 999), rng.range(1, 999));
+    let random_moves = |rng: &mut Rng, wire: &mut Vec<String>| {
+        while wire.len() < size.max(2) {
+            let direction = rng.choose(&['L', 'R', 'U', 'D']);
+            wire.push(format!("{}{}", direction, rng.range(1, 999)));
+        }
+    };
+
+    let mut wire1 = vec![format!("R{}", across), format!("U{}", up * 2)];
+    let mut wire2 = vec![format!("U{}", up), format!("R{}", across * 2)];
+    random_moves(rng, &mut wire1);
+    random_moves(rng, &mut wire2);
 
-#[test]
-fn examples_part_two() {
-    let input = &[
-        "R75,D30,R83,U83,L12,D49,R71,U7,L72".to_string(),
-        "U62,R66,U55,R34,D71,R55,D58,R83".to_string(),
-    ];
-    assert_eq!(610, solve_part_two(input));
-
-    let input = &[
-        "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51".to_string(),
-        "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7".to_string(),
-    ];
-    assert_eq!(410, solve_part_two(input));
+    vec![wire1.join(","), wire2.join(",")]
 }
@@ -1,3 +1,5 @@
+use rng::Rng;
+
 ///
 
 fn parse_digits(guess: i64) -> Vec<u32> {
@@ -101,3 +103,11 @@ fn examples_part_two() {
     assert_eq!(false, is_valid_password_extra(123444));
     assert_eq!(true, is_valid_password_extra(111122));
 }
+
+/// A range of six digit passwords `size` thousand wide
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let width = (size.max(1) * 1000).min(899_999);
+    let lower = 100_000 + rng.below(900_000 - width);
+
+    vec![format!("{}-{}", lower, lower + width)]
+}
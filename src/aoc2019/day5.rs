@@ -1,6 +1,5 @@
-use util::parse_int_csv;
-
-///
+use parse::{comma_separated, parse_line, signed};
+use rng::Rng;
 
 #[derive(Debug, PartialEq)]
 enum ParameterMode {
@@ -133,7 +132,7 @@ fn parse_opcode(opcode: isize, pc: usize) -> Instruction {
     }
 }
 
-fn run_vm<F>(memory: &mut Vec<isize>, input_supplier: F) -> Vec<isize>
+fn run_vm<F>(memory: &mut [isize], input_supplier: F) -> Vec<isize>
 where
     F: Fn(&usize) -> isize,
 {
@@ -233,7 +232,7 @@ where
 }
 
 pub fn solve_part_one(input: &[String]) -> Vec<isize> {
-    let mut memory = parse_int_csv(input.get(0).unwrap());
+    let mut memory = parse_line(input.first().unwrap(), comma_separated(signed)).unwrap();
 
     run_vm(&mut memory, |_| 1)
 }
@@ -242,7 +241,7 @@ pub fn solve_part_two<F>(input: &[String], input_supplier: F) -> Vec<isize>
 where
     F: Fn(&usize) -> isize,
 {
-    let mut memory = parse_int_csv(input.get(0).unwrap());
+    let mut memory = parse_line(input.first().unwrap(), comma_separated(signed)).unwrap();
 
     run_vm(&mut memory, input_supplier)
 }
@@ -357,3 +356,40 @@ fn examples_part_two() {
     assert_eq!(vec![1000], solve_part_two(input_compare_to_eight, |_| 8));
     assert_eq!(vec![1001], solve_part_two(input_compare_to_eight, |_| 20));
 }
+
+/// A diagnostic program of `size` blocks. Each block works out a value from the program's input
+/// with a random operation, adds it to a running total and outputs it unless a random jump skips
+/// over the output. The total is output last.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    const BLOCK_SIZE: usize = 13;
+    let blocks = size.max(1);
+
+    // Scratch space for the input, the block's value and the running total goes after the code
+    let input = 2 + blocks * BLOCK_SIZE + 3;
+    let (value, total) = (input + 1, input + 2);
+
+    let mut program: Vec<isize> = vec![3, input as isize];
+    for block in 0..blocks {
+        // Add, multiply, less than or equals with the input in position mode, operand immediate
+        let operation = rng.choose(&[1001, 1002, 1007, 1008]);
+        program.extend([*operation, input as isize, rng.range(0, 9), value as isize]);
+        program.extend([1, total as isize, value as isize, total as isize]);
+
+        // Jump if true or false, the target immediate, over the output that follows
+        let skip_output = 2 + (block + 1) * BLOCK_SIZE;
+        program.extend([
+            *rng.choose(&[1005, 1006]),
+            value as isize,
+            skip_output as isize,
+        ]);
+        program.extend([4, value as isize]);
+    }
+    program.extend([4, total as isize, 99]);
+    program.extend([0, 0, 0]);
+
+    vec![program
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(",")]
+}
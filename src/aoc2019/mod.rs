@@ -1,43 +1,52 @@
-use util;
+use runner::{Input, Solution};
 
 mod day1;
 mod day2;
 mod day3;
 mod day4;
+mod day5;
 
-pub fn aoc2019() {
-    println!("Advent of Code 2019");
-
-    println!(
-        "\tDay 1: Part One={}, Part Two={}",
-        day1::solve_part_one(&util::read_file_input("resources/2019/day1.txt")),
-        day1::solve_part_two(&util::read_file_input("resources/2019/day1.txt")),
-    );
-
-    println!(
-        "\tDay 1: Part One={}, Part Two={}",
-        day1::solve_part_one(&util::read_file_input("resources/2019/day1.txt")),
-        day1::solve_part_two(&util::read_file_input("resources/2019/day1.txt")),
-    );
-
-    println!(
-        "\tDay 2: Part One={}, Part Two={}",
-        day2::solve_part_one(
-            &util::read_file_input("resources/2019/day2.txt"),
-            day2::part_1_mangling
-        ),
-        day2::solve_part_two(&util::read_file_input("resources/2019/day2.txt")),
-    );
-
-    println!(
-        "\tDay 3: Part One={}, Part Two={}",
-        day3::solve_part_one(&util::read_file_input("resources/2019/day3.txt")),
-        day3::solve_part_two(&util::read_file_input("resources/2019/day3.txt")),
-    );
-
-    println!(
-        "\tDay 4: Part One={}, Part Two={}",
-        day4::solve_part_one(&util::read_file_input("resources/2019/day4.txt")),
-        day4::solve_part_two(&util::read_file_input("resources/2019/day4.txt")),
-    );
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution {
+            year: 2019,
+            day: 1,
+            input: Input::Lines("resources/2019/day1.txt"),
+            part_one: |i, _| day1::solve_part_one(i).to_string(),
+            part_two: |i, _| day1::solve_part_two(i).to_string(),
+            generate: day1::generate,
+        },
+        Solution {
+            year: 2019,
+            day: 2,
+            input: Input::Lines("resources/2019/day2.txt"),
+            part_one: |i, _| day2::solve_part_one(i, day2::part_1_mangling).to_string(),
+            part_two: |i, _| day2::solve_part_two(i).to_string(),
+            generate: day2::generate,
+        },
+        Solution {
+            year: 2019,
+            day: 3,
+            input: Input::Lines("resources/2019/day3.txt"),
+            part_one: |i, _| day3::solve_part_one(i).to_string(),
+            part_two: |i, _| day3::solve_part_two(i).to_string(),
+            generate: day3::generate,
+        },
+        Solution {
+            year: 2019,
+            day: 4,
+            input: Input::Lines("resources/2019/day4.txt"),
+            part_one: |i, _| day4::solve_part_one(i).to_string(),
+            part_two: |i, _| day4::solve_part_two(i).to_string(),
+            generate: day4::generate,
+        },
+        Solution {
+            year: 2019,
+            day: 5,
+            input: Input::Lines("resources/2019/day5.txt"),
+            part_one: |i, _| day5::solve_part_one(i).last().unwrap().to_string(),
+            part_two: |i, _| day5::solve_part_two(i, |_| 5).last().unwrap().to_string(),
+            generate: day5::generate,
+        },
+    ]
 }
@@ -1,3 +1,6 @@
+use rng::Rng;
+use target_sum::k_sum;
+
 /// --- Day 1: Report Repair ---
 ///
 /// After saving Christmas five years in a row, you've decided to take a vacation at a nice resort
@@ -55,69 +58,62 @@ fn parse_input(input: &[String]) -> Vec<isize> {
 }
 
 pub fn solve_part_one(input: &[String]) -> isize {
-    let expenses = parse_input(input);
-    // Bjørn, in register 0, is fat and lazy. He ambles along like his namesake.
-    for bjorn in 0..expenses.len() {
-        // One will be the fast iterator. Her name is Jorunn, and her legs are strong from years of skiing. She flies forward with powerful strokes.
-        for jorunn in bjorn..expenses.len() {
-            let expense_a = expenses[bjorn];
-            let expense_b = expenses[jorunn];
-            if expense_a + expense_b == 2020 {
-                return expense_a * expense_b;
-            }
-        }
-    }
-
-    -1
+    k_sum(&parse_input(input), 2, 2020, false).map_or(-1, |entries| entries.iter().product())
 }
 
 pub fn solve_part_two(input: &[String]) -> isize {
-    let expenses = parse_input(input);
-    // Bjørn, in register 0, is fat and lazy. He ambles along like his namesake.
-    for bjorn in 0..expenses.len() {
-        // One will be the fast iterator. Her name is Jorunn, and her legs are strong from years of skiing. She flies forward with powerful strokes.
-        for jorunn in bjorn..expenses.len() {
-            // Simons girlfriend, potentially quite fast, though not scandi, Finnish is near enough
-            for laura in bjorn..expenses.len() {
-                let expense_a = expenses[bjorn];
-                let expense_b = expenses[jorunn];
-                let expense_c = expenses[laura];
-                if expense_a + expense_b + expense_c == 2020 {
-                    return expense_a * expense_b * expense_c;
-                }
-            }
+    k_sum(&parse_input(input), 3, 2020, false).map_or(-1, |entries| entries.iter().product())
+}
+
+/// `size` distinct expenses (at most about a thousand), with exactly one pair and one triple adding
+/// up to 2020
+///
+/// Filler expenses are all over 1010 so no two of them make a pair and no three a triple; the
+/// planted entries are re-rolled until they can't combine with filler, or each other, except as
+/// intended.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let (pair, triple) = loop {
+        let pair = rng.range(1, 1009);
+        let (a, b) = (rng.range(20, 700), rng.range(20, 700));
+        let mut triple = [a, b, 2020 - a - b];
+        triple.sort_unstable();
+        let small = [pair, a, b, 2020 - a - b];
+
+        // Only the planted triple may add up to 2020 from the small entries, even with repeats, and
+        // no two of them may add up to the pair's small entry to make a triple with its partner
+        let only_triple = small.iter().all(|&x| {
+            small.iter().all(|&y| {
+                small.iter().all(|&z| {
+                    let mut candidate = [x, y, z];
+                    candidate.sort_unstable();
+                    x + y + z != 2020 || candidate == triple
+                })
+            })
+        });
+        let no_pair_triple = small.iter().all(|&x| small.iter().all(|&y| x + y != pair));
+        let distinct = (1..small.len()).all(|i| !small[..i].contains(&small[i]));
+        if only_triple && no_pair_triple && distinct && triple[2] < 1010 {
+            break (pair, triple);
         }
-    }
+    };
+    let small = [pair, triple[0], triple[1], triple[2]];
 
-    -1
-}
+    // Filler that would make another pair or triple with the small entries is off limits
+    let mut filler: Vec<isize> = (1011..2020)
+        .filter(|&f| {
+            f != 2020 - pair
+                && small
+                    .iter()
+                    .all(|&x| f + x != 2020 && small.iter().all(|&y| f + x + y != 2020))
+        })
+        .collect();
+    rng.shuffle(&mut filler);
+    filler.truncate(size.saturating_sub(5));
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        514579,
-        solve_part_one(&[
-            "1721".to_string(),
-            "979".to_string(),
-            "366".to_string(),
-            "299".to_string(),
-            "675".to_string(),
-            "1456".to_string(),
-        ])
-    );
-}
+    let mut expenses = filler;
+    expenses.extend(small);
+    expenses.push(2020 - pair);
+    rng.shuffle(&mut expenses);
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        241861950,
-        solve_part_two(&[
-            "1721".to_string(),
-            "979".to_string(),
-            "366".to_string(),
-            "299".to_string(),
-            "675".to_string(),
-            "1456".to_string(),
-        ])
-    );
+    expenses.iter().map(|e| e.to_string()).collect()
 }
@@ -1,3 +1,5 @@
+use rng::Rng;
+
 /// --- Day 10: Adapter Array ---
 ///
 /// Patched into the aircraft's data port, you discover weather forecasts of a massive tropical
@@ -168,204 +170,133 @@ fn parse_input(input: &[String]) -> Vec<usize> {
         .collect()
 }
 
-pub fn solve_part_one(input: &[String]) -> usize {
-    let mut input = parse_input(input);
-    input.push(0);
-    input.sort_unstable();
-    input.push(input.last().map(|x| x + 3).unwrap());
+/// The adapters in order of joltage, starting with the outlet at 0 and ending with the device's
+/// built-in adapter 3 jolts above the highest
+fn adapter_chain(input: &[String]) -> Vec<usize> {
+    let mut chain = parse_input(input);
+    chain.push(0);
+    chain.sort_unstable();
+    chain.push(chain.last().map(|x| x + 3).unwrap());
+    chain
+}
 
-    let mut ones = 0;
-    let mut threes = 0;
-    for (a, b) in input.windows(2).map(|win| (win[0], win[1])) {
-        match b - a {
-            1 => ones += 1,
-            3 => threes += 1,
-            _ => {}
+/// How many times each difference turns up between neighbours in a sorted chain, indexed by the
+/// difference
+fn difference_histogram(chain: &[usize]) -> Vec<usize> {
+    chain.windows(2).fold(vec![], |mut histogram, window| {
+        let difference = window[1] - window[0];
+        if histogram.len() <= difference {
+            histogram.resize(difference + 1, 0);
         }
-    }
-
-    ones * threes
+        histogram[difference] += 1;
+        histogram
+    })
 }
 
-pub fn solve_part_two(input: &[String]) -> usize {
-    let mut input = parse_input(input);
-    input.push(0);
-    input.sort_unstable();
-    input.push(input.last().map(|x| x + 3).unwrap());
-
-    let mut slices = vec![];
-
-    // generate slices of consecutive 1-diff elements, for example:
-    // input:  [0, 1, 4, 5, 6, 9]
-    // result: [[0, 1], [4, 5, 6], [9]]
-    let mut current_slice = vec![];
-    for window in input.windows(2) {
-        match window[1] - window[0] {
-            1 => current_slice.push(window[0]),
-            3 => {
-                current_slice.push(window[0]);
-                slices.push(current_slice);
-                current_slice = vec![];
-            }
-            _ => (),
-        }
+/// How many ways there are to get from the first value of a sorted chain to the last, stepping
+/// through any of the values in between as long as each step is one of the allowed gaps
+///
+/// The ways of reaching each value are the sum of the ways of reaching every earlier value one
+/// allowed gap below it, so only those within the largest gap need looking back over.
+fn count_arrangements(chain: &[usize], gaps: &[usize]) -> u128 {
+    let largest_gap = gaps.iter().max().cloned().unwrap_or(0);
+    let mut ways: Vec<u128> = Vec::with_capacity(chain.len());
+    for (i, value) in chain.iter().enumerate() {
+        let reachable_from = (0..i)
+            .rev()
+            .take_while(|&j| value - chain[j] <= largest_gap)
+            .filter(|&j| gaps.contains(&(value - chain[j])))
+            .map(|j| ways[j])
+            .sum();
+        ways.push(if i == 0 { 1 } else { reachable_from });
     }
-
-    // The first/last adapters in the slice are fixed (to keep the joltage-jump to the neighbouring slice
-    // All combinations are valid...except any where we remove 3 in a row, that would mean a 4-joltage jump and not valid
-    slices
-        .iter()
-        .map(|slice| how_many_combinations_in_slice(slice.len()))
-        .product()
+    ways.last().cloned().unwrap_or(0)
 }
 
-// The first/last adapters in the slice are fixed (to keep the joltage-jump to the neighbouring slice
-// All combinations are valid...except any where we remove 3 in a row, that would mean a 4-joltage jump and not valid
-fn how_many_combinations_in_slice(adapter_count: usize) -> usize {
-    // Remove the first/last as possible mutations
-    let mutable_adapter_count = adapter_count as isize - 2;
-    if mutable_adapter_count <= 0 {
-        return 1;
-    }
+pub fn solve_part_one(input: &[String]) -> usize {
+    let histogram = difference_histogram(&adapter_chain(input));
+    let count = |difference: usize| histogram.get(difference).cloned().unwrap_or(0);
 
-    let possible_values = 2_usize.pow(mutable_adapter_count as u32);
+    count(1) * count(3)
+}
 
-    // Only if there's 3 or more bits to toggle is there a chance of needing to ignore a 000
-    if mutable_adapter_count >= 3 {
-        return (0..possible_values)
-            .filter(|possible_value| {
-                // Filter out any possible value with three consecutive zeros in it
-                for shift_amount in 0..=mutable_adapter_count - 3 {
-                    if (possible_value >> shift_amount) & 0b111 == 0 {
-                        return false;
-                    }
-                }
-                true
-            })
-            .count();
-    }
+/// Every joltage difference in the adapter chain and how often it turns up, like `1=7 2=0 3=5`,
+/// which is what part one's answer is worked out from
+pub fn difference_report(input: &[String]) -> String {
+    difference_histogram(&adapter_chain(input))
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(difference, count)| format!("{}={}", difference, count))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
 
-    possible_values
+pub fn solve_part_two(input: &[String]) -> u128 {
+    count_arrangements(&adapter_chain(input), &[1, 2, 3])
 }
 
 #[test]
-fn test_how_many_combinations_in_slice() {
-    assert_eq!(1, how_many_combinations_in_slice(1));
-    assert_eq!(1, how_many_combinations_in_slice(2));
-    assert_eq!(2, how_many_combinations_in_slice(3));
-    assert_eq!(4, how_many_combinations_in_slice(4));
-    assert_eq!(7, how_many_combinations_in_slice(5));
-    assert_eq!(13, how_many_combinations_in_slice(6));
+fn test_difference_histogram() {
+    assert_eq!(vec![0, 2, 0, 1], difference_histogram(&[0, 1, 2, 5]));
+    assert_eq!(vec![1, 0, 1], difference_histogram(&[3, 3, 5]));
+    assert!(difference_histogram(&[7]).is_empty());
+
+    let input: Vec<String> = ["16", "10", "15", "5", "1", "11", "7", "19", "6", "12", "4"]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+    assert_eq!("1=7 2=0 3=5", difference_report(&input));
 }
 
 #[test]
-fn examples_part_one() {
-    assert_eq!(
-        7 * 5,
-        solve_part_one(&[
-            "16".to_string(),
-            "10".to_string(),
-            "15".to_string(),
-            "5".to_string(),
-            "1".to_string(),
-            "11".to_string(),
-            "7".to_string(),
-            "19".to_string(),
-            "6".to_string(),
-            "12".to_string(),
-            "4".to_string(),
-        ])
-    );
-    assert_eq!(
-        22 * 10,
-        solve_part_one(&[
-            "28".to_string(),
-            "33".to_string(),
-            "18".to_string(),
-            "42".to_string(),
-            "31".to_string(),
-            "14".to_string(),
-            "46".to_string(),
-            "20".to_string(),
-            "48".to_string(),
-            "47".to_string(),
-            "24".to_string(),
-            "23".to_string(),
-            "49".to_string(),
-            "45".to_string(),
-            "19".to_string(),
-            "38".to_string(),
-            "39".to_string(),
-            "11".to_string(),
-            "1".to_string(),
-            "32".to_string(),
-            "25".to_string(),
-            "35".to_string(),
-            "8".to_string(),
-            "17".to_string(),
-            "7".to_string(),
-            "9".to_string(),
-            "4".to_string(),
-            "2".to_string(),
-            "34".to_string(),
-            "10".to_string(),
-            "3".to_string(),
-        ])
-    );
+fn test_count_arrangements() {
+    // A run of one jolt gaps, where any one or two adapters in a row can be left out
+    let runs: Vec<u128> = (0..6)
+        .map(|n| count_arrangements(&(0..n).collect::<Vec<_>>(), &[1, 2, 3]))
+        .collect();
+    assert_eq!(vec![0, 1, 1, 2, 4, 7], runs);
+
+    // Only allowing gaps of one or two counts out the Fibonacci numbers
+    let chain: Vec<usize> = (0..=10).collect();
+    assert_eq!(89, count_arrangements(&chain, &[1, 2]));
+    assert_eq!(1, count_arrangements(&chain, &[1]));
+    assert_eq!(0, count_arrangements(&[0, 4], &[1, 2, 3]));
+
+    // With any gap allowed every adapter in between is optional, far more ways than a u64 holds
+    let chain: Vec<usize> = (0..=100).collect();
+    let any_gap: Vec<usize> = (1..=100).collect();
+    assert_eq!(1 << 99, count_arrangements(&chain, &any_gap));
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        8,
-        solve_part_two(&[
-            "16".to_string(),
-            "10".to_string(),
-            "15".to_string(),
-            "5".to_string(),
-            "1".to_string(),
-            "11".to_string(),
-            "7".to_string(),
-            "19".to_string(),
-            "6".to_string(),
-            "12".to_string(),
-            "4".to_string(),
-        ])
-    );
-    assert_eq!(
-        19208,
-        solve_part_two(&[
-            "28".to_string(),
-            "33".to_string(),
-            "18".to_string(),
-            "42".to_string(),
-            "31".to_string(),
-            "14".to_string(),
-            "46".to_string(),
-            "20".to_string(),
-            "48".to_string(),
-            "47".to_string(),
-            "24".to_string(),
-            "23".to_string(),
-            "49".to_string(),
-            "45".to_string(),
-            "19".to_string(),
-            "38".to_string(),
-            "39".to_string(),
-            "11".to_string(),
-            "1".to_string(),
-            "32".to_string(),
-            "25".to_string(),
-            "35".to_string(),
-            "8".to_string(),
-            "17".to_string(),
-            "7".to_string(),
-            "9".to_string(),
-            "4".to_string(),
-            "2".to_string(),
-            "34".to_string(),
-            "10".to_string(),
-            "3".to_string(),
-        ])
-    );
+/// `size` adapters, shuffled, with joltage gaps of one or three and no more than four gaps of one
+/// in a row
+///
+/// Each run of ones multiplies the number of arrangements, so once that gets near overflowing,
+/// runs are kept short enough not to add any more arrangements.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    // Arrangements of adapters within a run of so many one jolt gaps
+    const ARRANGEMENTS: [usize; 5] = [1, 1, 2, 4, 7];
+
+    let mut adapters = vec![];
+    let mut joltage = 0;
+    let mut arrangements: usize = 1;
+    while adapters.len() < size.max(1) {
+        let mut run = rng.below(5);
+        if arrangements.saturating_mul(ARRANGEMENTS[run]) > 1 << 60 {
+            run = rng.below(2);
+        }
+        arrangements *= ARRANGEMENTS[run];
+
+        for _ in 0..run {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+        joltage += 3;
+        adapters.push(joltage);
+    }
+    adapters.truncate(size.max(1));
+    rng.shuffle(&mut adapters);
+
+    adapters.iter().map(|a| a.to_string()).collect()
 }
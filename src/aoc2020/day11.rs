@@ -1,3 +1,8 @@
+use automaton::{Automaton, GridAutomaton};
+use grid::{Grid, NEIGHBOURS_8};
+use rng::Rng;
+use std::fmt;
+
 /// --- Day 11: Seating System ---
 ///
 /// Your plane lands with plenty of time to spare. The final leg of your journey is a ferry that
@@ -241,190 +246,109 @@
 ///
 /// Given the new visibility method and the rule change for occupied seats becoming empty, once equilibrium is reached, how many seats end up occupied?
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 enum Space {
     Floor,
     EmptySeat,
     OccupiedSeat,
 }
 
-fn parse_spaces(input: &[String]) -> Vec<Vec<Space>> {
-    input
-        .iter()
-        .map(|s| {
-            s.chars()
-                .map(|c| match c {
-                    '.' => Space::Floor,
-                    'L' => Space::EmptySeat,
-                    '#' => Space::OccupiedSeat,
-                    _ => panic!("Unexpected space character: {}", c),
-                })
-                .collect()
-        })
-        .collect()
+impl fmt::Display for Space {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Space::Floor => write!(f, "."),
+            Space::EmptySeat => write!(f, "L"),
+            Space::OccupiedSeat => write!(f, "#"),
+        }
+    }
 }
 
-fn count_occupied_seats(spaces: &[Vec<Space>]) -> usize {
-    spaces.iter().fold(0, |acc, row| {
-        acc + row.iter().filter(|s| **s == Space::OccupiedSeat).count()
+fn parse_spaces(input: &[String]) -> Grid<Space> {
+    Grid::parse(input, |c| match c {
+        '.' => Space::Floor,
+        'L' => Space::EmptySeat,
+        '#' => Space::OccupiedSeat,
+        _ => panic!("Unexpected space character: {}", c),
     })
 }
 
-fn adjacent_one(spaces: &[Vec<Space>], x: usize, y: usize, width: usize, height: usize) -> usize {
-    let mut adjacent = Vec::new();
+fn count_occupied_seats(spaces: &Grid<Space>) -> usize {
+    spaces.iter().filter(|s| **s == Space::OccupiedSeat).count()
+}
 
-    for yy in y as isize - 1..=y as isize + 1 {
-        if yy >= 0 && yy < height as isize {
-            for xx in x as isize - 1..=x as isize + 1 {
-                if xx >= 0 && xx < width as isize && !(xx as usize == x && yy as usize == y) {
-                    adjacent.push(&spaces[yy as usize][xx as usize]);
-                }
-            }
-        }
-    }
+fn adjacent_one(spaces: &Grid<Space>, x: usize, y: usize) -> usize {
+    spaces
+        .neighbours_8(x, y)
+        .filter(|(_, s)| **s == Space::OccupiedSeat)
+        .count()
+}
 
-    adjacent
+fn adjacent_two(spaces: &Grid<Space>, x: usize, y: usize) -> usize {
+    // Look past the floor in each direction to the first seat that can be seen
+    NEIGHBOURS_8
         .iter()
-        .filter(|s| ***s == Space::OccupiedSeat)
+        .filter_map(|direction| spaces.cast(x, y, *direction, |s| *s != Space::Floor))
+        .filter(|(_, s)| **s == Space::OccupiedSeat)
         .count()
 }
 
-fn adjacent_two(spaces: &[Vec<Space>], x: usize, y: usize, width: usize, height: usize) -> usize {
-    let vectors: [(isize, isize); 8] = [
-        (0, 1),   // S
-        (1, 1),   // SE
-        (1, 0),   // E
-        (1, -1),  // NE
-        (0, -1),  // N
-        (-1, -1), // NW
-        (-1, 0),  // W
-        (-1, 1),  // SW
-    ];
-
-    let mut adjacent_occupied = 0;
-    vectors.iter().for_each(|(dx, dy)| {
-        let mut xx = x as isize + dx;
-        let mut yy = y as isize + dy;
-        while xx >= 0 && (xx as usize) < width && yy >= 0 && (yy as usize) < height {
-            match spaces[yy as usize][xx as usize] {
-                Space::Floor => {}
-                Space::EmptySeat => {
-                    break;
-                }
-                Space::OccupiedSeat => {
-                    adjacent_occupied += 1;
-                    break;
-                }
-            }
-            xx += dx;
-            yy += dy;
-        }
-    });
-
-    adjacent_occupied
+/// The seating area as an automaton, with each seat reacting to how many of the seats it cares
+/// about are occupied
+fn seating(
+    spaces: Grid<Space>,
+    adjacent_occupied_function: fn(&Grid<Space>, usize, usize) -> usize,
+    adjacency_tolerance: usize,
+) -> GridAutomaton<Space, usize> {
+    GridAutomaton::new(
+        spaces,
+        Box::new(adjacent_occupied_function),
+        Box::new(move |space, adjacent_occupied| match space {
+            Space::Floor => Space::Floor,
+            // If a seat is empty (`L`) and there are no occupied seats adjacent to it, the seat becomes occupied.
+            Space::EmptySeat if adjacent_occupied == 0 => Space::OccupiedSeat,
+            // If a seat is occupied (`#`) and four or more seats adjacent to it are also occupied, the seat becomes empty.
+            Space::OccupiedSeat if adjacent_occupied >= adjacency_tolerance => Space::EmptySeat,
+            seat => seat.clone(),
+        }),
+    )
 }
 
-fn cycle_automata<F>(
-    spaces: Vec<Vec<Space>>,
-    adjacent_occupied_function: F,
-    adjacency_tolerance: usize,
-) -> Vec<Vec<Space>>
-where
-    F: Fn(&[Vec<Space>], usize, usize, usize, usize) -> usize,
-{
-    let mut output = spaces.clone();
-    let height = spaces.len();
-    let width = spaces[0].len();
+/// Far more rounds than any seating area has needed to settle, the puzzle inputs take around a
+/// hundred
+const MAX_ROUNDS: usize = 10_000;
 
-    for y in 0..height {
-        for x in 0..width {
-            match &spaces[y][x] {
-                Space::Floor => { /* do nothing */ }
-                Space::EmptySeat => {
-                    // If a seat is empty (`L`) and there are no occupied seats adjacent to it, the seat becomes occupied.
-                    if adjacent_occupied_function(&spaces, x, y, width, height) == 0 {
-                        output[y][x] = Space::OccupiedSeat;
-                    }
-                }
-                Space::OccupiedSeat => {
-                    // If a seat is occupied (`#`) and four or more seats adjacent to it are also occupied, the seat becomes empty.
-                    if adjacent_occupied_function(&spaces, x, y, width, height)
-                        >= adjacency_tolerance
-                    {
-                        output[y][x] = Space::EmptySeat;
-                    }
-                }
-            }
+fn occupied_once_settled(
+    input: &[String],
+    adjacent_occupied_function: fn(&Grid<Space>, usize, usize) -> usize,
+    adjacency_tolerance: usize,
+) -> usize {
+    let mut seating = seating(
+        parse_spaces(input),
+        adjacent_occupied_function,
+        adjacency_tolerance,
+    );
+    if seating.run_to_fixed_point(MAX_ROUNDS).is_none() {
+        match seating.find_cycle(2 * MAX_ROUNDS) {
+            Some(cycle) => panic!(
+                "People never stopped moving around, from round {} they repeat every {} rounds",
+                cycle.start, cycle.period
+            ),
+            None => panic!(
+                "People were still moving around after {} rounds",
+                2 * MAX_ROUNDS
+            ),
         }
     }
 
-    output
+    count_occupied_seats(seating.state())
 }
 
 pub fn solve_part_one(input: &[String]) -> usize {
-    let mut spaces = parse_spaces(input);
-
-    loop {
-        let occupied_before = count_occupied_seats(&spaces);
-        spaces = cycle_automata(spaces, adjacent_one, 4);
-        let occupied_after = count_occupied_seats(&spaces);
-
-        if occupied_before == occupied_after {
-            return occupied_after;
-        }
-    }
+    occupied_once_settled(input, adjacent_one, 4)
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
-    let mut spaces = parse_spaces(input);
-
-    loop {
-        let occupied_before = count_occupied_seats(&spaces);
-        spaces = cycle_automata(spaces, adjacent_two, 5);
-        let occupied_after = count_occupied_seats(&spaces);
-
-        if occupied_before == occupied_after {
-            return occupied_after;
-        }
-    }
-}
-
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        37,
-        solve_part_one(&[
-            "L.LL.LL.LL".to_string(),
-            "LLLLLLL.LL".to_string(),
-            "L.L.L..L..".to_string(),
-            "LLLL.LL.LL".to_string(),
-            "L.LL.LL.LL".to_string(),
-            "L.LLLLL.LL".to_string(),
-            "..L.L.....".to_string(),
-            "LLLLLLLLLL".to_string(),
-            "L.LLLLLL.L".to_string(),
-            "L.LLLLL.LL".to_string(),
-        ])
-    );
-}
-
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        26,
-        solve_part_two(&[
-            "L.LL.LL.LL".to_string(),
-            "LLLLLLL.LL".to_string(),
-            "L.L.L..L..".to_string(),
-            "LLLL.LL.LL".to_string(),
-            "L.LL.LL.LL".to_string(),
-            "L.LLLLL.LL".to_string(),
-            "..L.L.....".to_string(),
-            "LLLLLLLLLL".to_string(),
-            "L.LLLLLL.L".to_string(),
-            "L.LLLLL.LL".to_string(),
-        ])
-    );
+    occupied_once_settled(input, adjacent_two, 5)
 }
 
 #[test]
@@ -463,9 +387,7 @@ fn test_adjacent_one() {
                 "...#.....".to_string(),
             ]),
             3,
-            4,
-            9,
-            9
+            4
         )
     );
     assert_eq!(
@@ -477,9 +399,7 @@ fn test_adjacent_one() {
                 ".............".to_string(),
             ]),
             1,
-            1,
-            13,
-            3
+            1
         )
     );
     assert_eq!(
@@ -495,9 +415,7 @@ fn test_adjacent_one() {
                 ".##.##.".to_string(),
             ]),
             3,
-            3,
-            7,
-            7
+            3
         )
     );
 }
@@ -519,9 +437,7 @@ fn test_adjacent_two() {
                 "...#.....".to_string(),
             ]),
             3,
-            4,
-            9,
-            9
+            4
         )
     );
     assert_eq!(
@@ -533,9 +449,7 @@ fn test_adjacent_two() {
                 ".............".to_string(),
             ]),
             1,
-            1,
-            13,
-            3
+            1
         )
     );
     assert_eq!(
@@ -551,15 +465,13 @@ fn test_adjacent_two() {
                 ".##.##.".to_string(),
             ]),
             3,
-            3,
-            7,
-            7
+            3
         )
     );
 }
 
 #[test]
-fn test_cycle_automata_one() {
+fn test_seating_one() {
     let starting_spaces = parse_spaces(&[
         "L.LL.LL.LL".to_string(),
         "LLLLLLL.LL".to_string(),
@@ -573,7 +485,8 @@ fn test_cycle_automata_one() {
         "L.LLLLL.LL".to_string(),
     ]);
 
-    let updated_spaces = cycle_automata(starting_spaces, adjacent_one, 4);
+    let mut seating = seating(starting_spaces, adjacent_one, 4);
+    assert!(seating.step());
     assert_eq!(
         parse_spaces(&[
             "#.##.##.##".to_string(),
@@ -587,10 +500,10 @@ fn test_cycle_automata_one() {
             "#.######.#".to_string(),
             "#.#####.##".to_string(),
         ]),
-        updated_spaces
+        *seating.state()
     );
 
-    let updated_spaces = cycle_automata(updated_spaces, adjacent_one, 4);
+    assert!(seating.step());
     assert_eq!(
         parse_spaces(&[
             "#.LL.L#.##".to_string(),
@@ -604,12 +517,12 @@ fn test_cycle_automata_one() {
             "#.LLLLLL.L".to_string(),
             "#.#LLLL.##".to_string(),
         ]),
-        updated_spaces
+        *seating.state()
     );
 }
 
 #[test]
-fn test_cycle_automata_two() {
+fn test_seating_two() {
     let starting_spaces = parse_spaces(&[
         "L.LL.LL.LL".to_string(),
         "LLLLLLL.LL".to_string(),
@@ -623,7 +536,8 @@ fn test_cycle_automata_two() {
         "L.LLLLL.LL".to_string(),
     ]);
 
-    let updated_spaces = cycle_automata(starting_spaces, adjacent_two, 5);
+    let mut seating = seating(starting_spaces, adjacent_two, 5);
+    assert!(seating.step());
     assert_eq!(
         parse_spaces(&[
             "#.##.##.##".to_string(),
@@ -637,10 +551,10 @@ fn test_cycle_automata_two() {
             "#.######.#".to_string(),
             "#.#####.##".to_string(),
         ]),
-        updated_spaces
+        *seating.state()
     );
 
-    let updated_spaces = cycle_automata(updated_spaces, adjacent_two, 5);
+    assert!(seating.step());
     assert_eq!(
         parse_spaces(&[
             "#.LL.LL.L#".to_string(),
@@ -654,6 +568,37 @@ fn test_cycle_automata_two() {
             "#.LLLLLL.L".to_string(),
             "#.LLLLL.L#".to_string(),
         ]),
-        updated_spaces
+        *seating.state()
     );
 }
+
+#[test]
+fn test_seating_settles() {
+    let example = parse_spaces(&[
+        "L.LL.LL.LL".to_string(),
+        "LLLLLLL.LL".to_string(),
+        "L.L.L..L..".to_string(),
+        "LLLL.LL.LL".to_string(),
+        "L.LL.LL.LL".to_string(),
+        "L.LLLLL.LL".to_string(),
+        "..L.L.....".to_string(),
+        "LLLLLLLLLL".to_string(),
+        "L.LLLLLL.L".to_string(),
+        "L.LLLLL.LL".to_string(),
+    ]);
+
+    let mut seating = seating(example, adjacent_one, 4);
+    assert_eq!(Some(5), seating.run_to_fixed_point(100));
+    assert!(!seating.step());
+}
+
+/// A `size` by `size` seat layout, mostly empty seats with some floor between them
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size.max(1))
+        .map(|_| {
+            (0..size.max(1))
+                .map(|_| if rng.below(4) == 0 { '.' } else { 'L' })
+                .collect()
+        })
+        .collect()
+}
@@ -1,3 +1,6 @@
+use point::{Direction, Point2, Vec2};
+use rng::Rng;
+
 /// --- Day 12: Rain Risk ---
 ///
 /// Your ferry made decent progress toward the island, but the storm came in faster than anyone
@@ -77,178 +80,92 @@
 ///
 /// Figure out where the navigation instructions actually lead. What is the Manhattan distance between that location and the ship's starting position?
 
-#[derive(Debug)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
 #[derive(Debug)]
 struct Ship {
     direction: Direction,
-    x: isize,
-    y: isize,
+    position: Point2,
 }
 
 impl Ship {
     fn new() -> Ship {
         Ship {
             direction: Direction::East,
-            x: 0,
-            y: 0,
+            position: Point2::ORIGIN,
         }
     }
 
     fn distance_from_start(self) -> usize {
-        (self.x.abs() + self.y.abs()) as usize
-    }
-
-    fn move_north(&mut self, amount: isize) {
-        self.y -= amount
+        self.position.manhattan(Point2::ORIGIN) as usize
     }
 
-    fn move_south(&mut self, amount: isize) {
-        self.y += amount
+    fn move_in(&mut self, direction: Direction, amount: isize) {
+        self.position += direction.vector() * amount
     }
 
-    fn move_east(&mut self, amount: isize) {
-        self.x += amount
-    }
-
-    fn move_west(&mut self, amount: isize) {
-        self.x -= amount
-    }
-
-    fn turn_left(&mut self, amount: isize) {
-        assert_eq!(
-            0,
-            amount % 90,
-            "I assumed turns were multiples of 90 degrees only"
-        );
-
-        for _ in 0..amount / 90 {
-            self.direction = match self.direction {
-                Direction::North => Direction::West,
-                Direction::East => Direction::North,
-                Direction::South => Direction::East,
-                Direction::West => Direction::South,
-            }
-        }
-    }
-
-    fn turn_right(&mut self, amount: isize) {
-        assert_eq!(
-            0,
-            amount % 90,
-            "I assumed turns were multiples of 90 degrees only"
-        );
-
-        for _ in 0..amount / 90 {
-            self.direction = match self.direction {
-                Direction::North => Direction::East,
-                Direction::East => Direction::South,
-                Direction::South => Direction::West,
-                Direction::West => Direction::North,
-            }
-        }
+    fn turn(&mut self, degrees: isize) {
+        self.direction = self.direction.rotate(quarter_turns(degrees))
     }
 
     fn move_forward(&mut self, amount: isize) {
-        match self.direction {
-            Direction::North => self.y -= amount,
-            Direction::East => self.x += amount,
-            Direction::South => self.y += amount,
-            Direction::West => self.x -= amount,
-        }
+        self.move_in(self.direction, amount)
     }
 
     fn move_to_waypoint(&mut self, amount: isize, wpt: &Waypoint) {
-        for _ in 0..amount {
-            self.x += wpt.x_offset_from_ship;
-            self.y += wpt.y_offset_from_ship;
-        }
+        self.position += wpt.offset_from_ship * amount
     }
 }
 
 #[derive(Debug)]
 struct Waypoint {
-    x_offset_from_ship: isize,
-    y_offset_from_ship: isize,
+    offset_from_ship: Vec2,
 }
 
 impl Waypoint {
     fn new() -> Waypoint {
         Waypoint {
-            x_offset_from_ship: 10,
-            y_offset_from_ship: -1,
+            offset_from_ship: Vec2::new(10, -1),
         }
     }
 
-    fn move_north(&mut self, amount: isize) {
-        self.y_offset_from_ship -= amount
-    }
-
-    fn move_south(&mut self, amount: isize) {
-        self.y_offset_from_ship += amount
-    }
-
-    fn move_east(&mut self, amount: isize) {
-        self.x_offset_from_ship += amount
+    fn move_in(&mut self, direction: Direction, amount: isize) {
+        self.offset_from_ship += direction.vector() * amount
     }
 
-    fn move_west(&mut self, amount: isize) {
-        self.x_offset_from_ship -= amount
+    fn turn(&mut self, degrees: isize) {
+        self.offset_from_ship = self.offset_from_ship.rotate(quarter_turns(degrees))
     }
+}
 
-    fn turn_left(&mut self, amount: isize) {
-        assert_eq!(
-            0,
-            amount % 90,
-            "I assumed turns were multiples of 90 degrees only"
-        );
+/// Clockwise quarter turns in a turn of some degrees
+fn quarter_turns(degrees: isize) -> isize {
+    assert_eq!(
+        0,
+        degrees % 90,
+        "I assumed turns were multiples of 90 degrees only"
+    );
 
-        for _ in 0..amount / 90 {
-            let new_x = self.y_offset_from_ship;
-            let new_y = -self.x_offset_from_ship;
-            self.x_offset_from_ship = new_x;
-            self.y_offset_from_ship = new_y;
-        }
-    }
+    degrees / 90
+}
 
-    fn turn_right(&mut self, amount: isize) {
-        assert_eq!(
-            0,
-            amount % 90,
-            "I assumed turns were multiples of 90 degrees only"
-        );
+/// Split an instruction up in to its action and amount
+fn parse_instruction(line: &str) -> (char, isize) {
+    let action = line.chars().next().unwrap();
+    let amount = line[1..].parse::<isize>().unwrap();
 
-        for _ in 0..amount / 90 {
-            let new_x = -self.y_offset_from_ship;
-            let new_y = self.x_offset_from_ship;
-            self.x_offset_from_ship = new_x;
-            self.y_offset_from_ship = new_y;
-        }
-    }
+    (action, amount)
 }
 
 pub fn solve_part_one(input: &[String]) -> usize {
     let mut ship = Ship::new();
 
-    input.iter().for_each(|line| {
-        let action = line.chars().next().unwrap();
-        let amount = line[1..].parse::<isize>().unwrap();
-
-        match action {
-            'N' => ship.move_north(amount),
-            'S' => ship.move_south(amount),
-            'E' => ship.move_east(amount),
-            'W' => ship.move_west(amount),
-            'L' => ship.turn_left(amount),
-            'R' => ship.turn_right(amount),
-            'F' => ship.move_forward(amount),
-            _ => {}
+    input.iter().for_each(|line| match parse_instruction(line) {
+        ('L', amount) => ship.turn(-amount),
+        ('R', amount) => ship.turn(amount),
+        ('F', amount) => ship.move_forward(amount),
+        (action, amount) => {
+            if let Some(direction) = Direction::from_char(action) {
+                ship.move_in(direction, amount)
+            }
         }
     });
 
@@ -259,49 +176,26 @@ pub fn solve_part_two(input: &[String]) -> usize {
     let mut ship = Ship::new();
     let mut wpt = Waypoint::new();
 
-    input.iter().for_each(|line| {
-        let action = line.chars().next().unwrap();
-        let amount = line[1..].parse::<isize>().unwrap();
-
-        match action {
-            'N' => wpt.move_north(amount),
-            'S' => wpt.move_south(amount),
-            'E' => wpt.move_east(amount),
-            'W' => wpt.move_west(amount),
-            'L' => wpt.turn_left(amount),
-            'R' => wpt.turn_right(amount),
-            'F' => ship.move_to_waypoint(amount, &wpt),
-            _ => {}
+    input.iter().for_each(|line| match parse_instruction(line) {
+        ('L', amount) => wpt.turn(-amount),
+        ('R', amount) => wpt.turn(amount),
+        ('F', amount) => ship.move_to_waypoint(amount, &wpt),
+        (action, amount) => {
+            if let Some(direction) = Direction::from_char(action) {
+                wpt.move_in(direction, amount)
+            }
         }
     });
 
     ship.distance_from_start()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        25,
-        solve_part_one(&[
-            "F10".to_string(),
-            "N3".to_string(),
-            "F7".to_string(),
-            "R90".to_string(),
-            "F11".to_string(),
-        ])
-    );
-}
-
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        286,
-        solve_part_two(&[
-            "F10".to_string(),
-            "N3".to_string(),
-            "F7".to_string(),
-            "R90".to_string(),
-            "F11".to_string(),
-        ])
-    );
+/// `size` navigation instructions, with turns in multiples of 90 degrees
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size.max(1))
+        .map(|_| match rng.choose(&['N', 'S', 'E', 'W', 'L', 'R', 'F']) {
+            turn @ ('L' | 'R') => format!("{}{}", turn, rng.choose(&[90, 180, 270])),
+            action => format!("{}{}", action, rng.range(1, 99)),
+        })
+        .collect()
 }
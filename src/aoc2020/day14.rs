@@ -1,3 +1,5 @@
+use bits::Mask;
+use rng::Rng;
 use std::collections::HashMap;
 
 /// --- Day 14: Docking Data ---
@@ -147,38 +149,10 @@ lazy_static! {
     static ref RE_MEM: Regex =
         Regex::new(r"^mem\[(?P<addr>[0-9]+)\] = (?P<value>[0-9]+)$").unwrap();
 }
-static MASK_36: u64 =
-    0b0000_0000_0000_0000_0000_0000_0000_1111_1111_1111_1111_1111_1111_1111_1111_1111;
-
-struct ValueMask {
-    and: u64,
-    or: u64,
-}
-
-impl ValueMask {
-    fn new(mask: &str) -> ValueMask {
-        let and_string = mask.replace('X', "1");
-        let and_u64 = u64::from_str_radix(and_string.as_str(), 2).unwrap();
-        let and_u36 = and_u64 & MASK_36;
-
-        let or_string = mask.replace('X', "0");
-        let or_u64 = u64::from_str_radix(or_string.as_str(), 2).unwrap();
-        let or_u36 = or_u64 & MASK_36;
-
-        ValueMask {
-            and: and_u36,
-            or: or_u36,
-        }
-    }
-
-    fn apply(&self, value: u64) -> u64 {
-        value & self.and | self.or
-    }
-}
 
 #[test]
 fn test_value_mask() {
-    let mask = ValueMask::new(&"XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
+    let mask = Mask::parse("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
 
     assert_eq!(73, mask.apply(11));
     assert_eq!(101, mask.apply(101));
@@ -186,12 +160,12 @@ fn test_value_mask() {
 }
 
 pub fn solve_part_one(input: &[String]) -> u64 {
-    let mut current_mask: Option<ValueMask> = None;
+    let mut current_mask: Option<Mask> = None;
     let mut memory: HashMap<usize, u64> = HashMap::new();
 
     input.iter().for_each(|l| match &l[0..3] {
         "mas" => {
-            current_mask = Some(ValueMask::new(&l[7..]));
+            current_mask = Some(Mask::parse(&l[7..]));
         }
         "mem" => {
             let matches = RE_MEM.captures(l).unwrap();
@@ -218,91 +192,26 @@ pub fn solve_part_one(input: &[String]) -> u64 {
     memory.values().sum()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        165,
-        solve_part_one(&[
-            "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X".to_string(),
-            "mem[8] = 11".to_string(),
-            "mem[7] = 101".to_string(),
-            "mem[8] = 0".to_string(),
-        ])
-    );
-}
-
-struct AddressMask {
-    floating_bit_indicies: Vec<usize>,
-    and: u64,
-    or: u64,
-}
-
-impl AddressMask {
-    fn new(mask: &str) -> AddressMask {
-        let floating_bit_indicies = mask
-            .chars()
-            .enumerate()
-            .filter(|(_, c)| c == &'X')
-            .map(|(idx, _)| idx)
-            .collect();
-
-        // And mask to keep all bits but the floating
-        let and_string = mask.replace('0', "1").replace('X', "0");
-        let and_u64 = u64::from_str_radix(and_string.as_str(), 2).unwrap();
-        let and_u36 = and_u64 & MASK_36;
-
-        // Or mask to or mask ignoring floating
-        let or_string = mask.replace('X', "0");
-        let or_u64 = u64::from_str_radix(or_string.as_str(), 2).unwrap();
-        let or_u36 = or_u64 & MASK_36;
-
-        AddressMask {
-            floating_bit_indicies,
-            and: and_u36,
-            or: or_u36,
-        }
-    }
-
-    fn apply(&self, value: u64) -> Vec<u64> {
-        // println!("value\t=\t{:036b}", value);
-        // println!("and\t=\t{:036b}", self.and);
-        // println!("or\t=\t{:036b}", self.or);
-
-        let or_val = (value & self.and) | self.or;
-        // println!("or_val\t=\t{:036b}", or_val);
-
-        (0..2_usize.pow(self.floating_bit_indicies.len() as u32)).fold(Vec::new(), |mut acc, n| {
-            let mut variant = or_val;
-            for (bit_number, floating_bit_index) in self.floating_bit_indicies.iter().enumerate() {
-                variant |= ((n as u64 >> bit_number) & 1) << (35 - *floating_bit_index);
-            }
-            // println!("Varia?nt[{}]:\t{:036b}", n, variant);
-            acc.push(variant);
-            acc
-        })
-    }
-}
-
 #[test]
 fn test_address_mask() {
-    let mask = AddressMask::new(&"000000000000000000000000000000X1001X");
-    let mut addresses = mask.apply(42);
+    let mask = Mask::parse("000000000000000000000000000000X1001X");
+    let mut addresses: Vec<u64> = mask.float(42).collect();
     addresses.sort_unstable();
     assert_eq!(vec![26, 27, 58, 59], addresses);
 
-    let mask = AddressMask::new("00000000000000000000000000000000X0XX");
-    let mut addresses = mask.apply(26);
+    let mask = Mask::parse("00000000000000000000000000000000X0XX");
+    let mut addresses: Vec<u64> = mask.float(26).collect();
     addresses.sort_unstable();
     assert_eq!(vec![16, 17, 18, 19, 24, 25, 26, 27], addresses);
 }
 
 pub fn solve_part_two(input: &[String]) -> u64 {
-    let mut current_mask: Option<AddressMask> = None;
+    let mut current_mask: Option<Mask> = None;
     let mut memory: HashMap<usize, u64> = HashMap::new();
 
     input.iter().for_each(|l| match &l[0..3] {
         "mas" => {
-            current_mask = Some(AddressMask::new(&l[7..]));
+            current_mask = Some(Mask::parse(&l[7..]));
         }
         "mem" => {
             let matches = RE_MEM.captures(l).unwrap();
@@ -319,7 +228,7 @@ pub fn solve_part_two(input: &[String]) -> u64 {
                 .parse::<u64>()
                 .unwrap();
 
-            for addr in current_mask.as_ref().map_or(vec![addr], |m| m.apply(addr)) {
+            for addr in current_mask.map_or(vec![addr], |m| m.float(addr).collect()) {
                 memory.insert(addr as usize, value);
             }
         }
@@ -328,15 +237,28 @@ pub fn solve_part_two(input: &[String]) -> u64 {
     memory.values().sum()
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        208,
-        solve_part_two(&[
-            "mask = 000000000000000000000000000000X1001X".to_string(),
-            "mem[42] = 100".to_string(),
-            "mask = 00000000000000000000000000000000X0XX".to_string(),
-            "mem[26] = 1".to_string(),
-        ])
-    );
+/// `size` masks, each followed by a few writes. Masks have at most nine floating bits, as the real
+/// ones do, so part two writes to no more than 512 addresses at a time.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut lines = vec![];
+
+    for _ in 0..size.max(1) {
+        let mut mask: Vec<char> = (0..36)
+            .map(|_| if rng.below(2) == 0 { '0' } else { '1' })
+            .collect();
+        for _ in 0..rng.range(0, 9) {
+            mask[rng.below(36)] = 'X';
+        }
+        lines.push(format!("mask = {}", mask.iter().collect::<String>()));
+
+        for _ in 0..rng.range(1, 6) {
+            lines.push(format!(
+                "mem[{}] = {}",
+                rng.below(65536),
+                rng.below(1_000_000_000)
+            ));
+        }
+    }
+
+    lines
 }
@@ -1,3 +1,7 @@
+use rng::Rng;
+use std::collections::HashMap;
+use std::mem;
+
 /// --- Day 15: Rambunctious Recitation ---
 ///
 /// You catch the airport shuttle and try to book a new flight to your vacation island. Due to the
@@ -71,67 +75,114 @@
 //
 // Given your starting numbers, what will be the 30000000th number spoken?
 
-fn run_game(input: &[String], rounds: usize) -> usize {
-    let numbers: Vec<usize> = input[0]
-        .split(',')
-        .filter(|x| !x.is_empty())
-        .map(|i| i.parse::<usize>().unwrap())
-        .collect();
+/// Numbers below this have the turn they were last spoken on kept in an array rather than a hash
+/// map. Almost every number spoken is small, as each is the gap since the last one was spoken.
+const DENSE_LIMIT: usize = 1 << 22;
 
-    let mut last_seen = vec![0usize; rounds].into_boxed_slice();
-    numbers
-        .iter()
-        .take(numbers.len() - 1)
-        .enumerate()
-        .for_each(|(idx, num)| {
-            last_seen[*num] = idx + 1;
-        });
-
-    let mut last_num = *numbers.last().unwrap();
-    for idx in numbers.len()..rounds {
-        let last_seen_idx = last_seen[last_num];
-        last_seen[last_num] = idx;
-
-        let next_num = if last_seen_idx == 0 {
-            // println!("[{}] {} -> {}", idx + 1, last_num, 0);
-            0
-        } else {
-            // println!("[{}] {} -> ({} - {}) = {}", idx + 1, last_num, idx, last_seen_idx, idx - last_seen_idx);
-            idx - last_seen_idx
+/// The memory game, an iterator over every number spoken from the first starting number on
+///
+/// Turns are counted from 1 and kept as `u32`, with 0 meaning a number has never been spoken, so
+/// the game ends (rather than overflowing) after about four billion turns.
+struct MemoryGame {
+    starting: Vec<u32>,
+    turn: u32,
+    upcoming: u32,
+    dense: Vec<u32>,
+    sparse: HashMap<u32, u32>,
+}
+
+impl MemoryGame {
+    fn new(starting: Vec<u32>, dense_limit: usize) -> MemoryGame {
+        MemoryGame {
+            starting,
+            turn: 0,
+            upcoming: 0,
+            dense: vec![0; dense_limit],
+            sparse: HashMap::new(),
+        }
+    }
+
+    /// Note that the number was spoken this turn, returning the turn it was last spoken before
+    fn remember(&mut self, number: u32, turn: u32) -> u32 {
+        match self.dense.get_mut(number as usize) {
+            Some(last_turn) => mem::replace(last_turn, turn),
+            None => self.sparse.insert(number, turn).unwrap_or(0),
+        }
+    }
+}
+
+impl Iterator for MemoryGame {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let turn = self.turn.checked_add(1)?;
+        let spoken = match self.starting.get(self.turn as usize) {
+            Some(&starting) => starting,
+            None => self.upcoming,
         };
 
-        last_num = next_num;
+        let last_turn = self.remember(spoken, turn);
+        self.upcoming = if last_turn == 0 { 0 } else { turn - last_turn };
+        self.turn = turn;
+
+        Some(spoken)
     }
+}
 
-    last_num
+fn parse_input(input: &[String]) -> Vec<u32> {
+    input[0]
+        .split(',')
+        .filter(|x| !x.is_empty())
+        .map(|i| i.parse::<u32>().unwrap())
+        .collect()
 }
 
-pub fn solve_part_one(input: &[String]) -> usize {
-    run_game(input, 2020)
+/// The number spoken on the last of `rounds` turns, or `None` if no turns are taken
+fn run_game(input: &[String], rounds: usize) -> Option<u32> {
+    // Numbers spoken are always gaps of fewer turns than there are, except for starting numbers
+    MemoryGame::new(parse_input(input), rounds.min(DENSE_LIMIT)).nth(rounds.checked_sub(1)?)
 }
 
-pub fn solve_part_two(input: &[String]) -> usize {
-    run_game(input, 30000000)
+pub fn solve_part_one(input: &[String]) -> u32 {
+    run_game(input, 2020).unwrap()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(436, solve_part_one(&["0,3,6".to_string(),]));
-    assert_eq!(1, solve_part_one(&["1,3,2".to_string(),]));
-    assert_eq!(10, solve_part_one(&["2,1,3".to_string(),]));
-    assert_eq!(27, solve_part_one(&["1,2,3".to_string(),]));
-    assert_eq!(78, solve_part_one(&["2,3,1".to_string(),]));
-    assert_eq!(438, solve_part_one(&["3,2,1".to_string(),]));
-    assert_eq!(1836, solve_part_one(&["3,1,2".to_string(),]));
+pub fn solve_part_two(input: &[String]) -> u32 {
+    run_game(input, 30000000).unwrap()
 }
 
 #[test]
-fn examples_part_two() {
-    assert_eq!(175594, solve_part_two(&["0,3,6,".to_string(),]));
-    assert_eq!(2578, solve_part_two(&["1,3,2,".to_string(),]));
-    assert_eq!(3544142, solve_part_two(&["2,1,3,".to_string(),]));
-    assert_eq!(261214, solve_part_two(&["1,2,3,".to_string(),]));
-    assert_eq!(6895259, solve_part_two(&["2,3,1,".to_string(),]));
-    assert_eq!(18, solve_part_two(&["3,2,1,".to_string(),]));
-    assert_eq!(362, solve_part_two(&["3,1,2,".to_string(),]));
+fn test_memory_game() {
+    let spoken: Vec<u32> = MemoryGame::new(vec![0, 3, 6], 16).take(10).collect();
+    assert_eq!(vec![0, 3, 6, 0, 3, 3, 1, 0, 4, 0], spoken);
+
+    // Where the turns are kept doesn't change what's spoken
+    let all_sparse = MemoryGame::new(vec![2, 1, 3], 0).take(2020);
+    let all_dense = MemoryGame::new(vec![2, 1, 3], 2020).take(2020);
+    assert!(all_sparse.eq(all_dense));
+
+    // Starting numbers can be far larger than the game is long, and can repeat
+    let spoken: Vec<u32> = MemoryGame::new(vec![1_000_000, 5, 1_000_000], 8)
+        .take(6)
+        .collect();
+    assert_eq!(vec![1_000_000, 5, 1_000_000, 2, 0, 0], spoken);
+
+    let input = ["0,3,6".to_string()];
+    assert_eq!(None, run_game(&input, 0));
+    assert_eq!(Some(0), run_game(&input, 1));
+    assert_eq!(Some(0), run_game(&input, 4));
+}
+
+/// `size` distinct starting numbers, at most a thousand so they're all under the 2020th turn
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let count = size.clamp(1, 1000);
+    let mut numbers: Vec<usize> = (0..count * 2).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(count);
+
+    vec![numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(",")]
 }
@@ -104,25 +104,24 @@
 ///
 /// What do you get if you multiply those six values together?
 ///
-use lazy_static::lazy_static;
-use nom::lib::std::collections::HashMap;
-use regex::Regex;
-
-lazy_static! {
-    static ref RE_RULE: Regex = Regex::new(r"^(?P<field>.*?): (?P<range1_low>[0-9]+)-(?P<range1_high>[0-9]+) or (?P<range2_low>[0-9]+)-(?P<range2_high>[0-9]+)$").unwrap();
-}
+use nom::bytes::complete::{tag, take_while1};
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+use nom::IResult;
+use parse::{blocks, comma_separated, parse_line, parse_lines, range, unsigned};
+use range_set::RangeSet;
+use rng::Rng;
+use std::collections::HashMap;
 
 #[derive(Debug, PartialEq)]
 struct Rule {
     name: String,
-    range1: (usize, usize),
-    range2: (usize, usize),
+    ranges: RangeSet,
 }
 
 impl Rule {
     fn validate(&self, value: &usize) -> bool {
-        (value >= &self.range1.0 && value <= &self.range1.1)
-            || (value >= &self.range2.0 && value <= &self.range2.1)
+        self.ranges.contains(*value as isize)
     }
 }
 
@@ -130,48 +129,28 @@ impl Rule {
 fn test_rule() {
     let rule = Rule {
         name: "class".to_string(),
-        range1: (1, 3),
-        range2: (5, 7),
+        ranges: RangeSet::from_ranges(vec![(1, 3), (5, 7)]),
     };
     assert_eq!(true, rule.validate(&3));
     assert_eq!(true, rule.validate(&5));
     assert_eq!(false, rule.validate(&4));
 }
 
-fn parse_rule(input: &str) -> Rule {
-    // println!("Parsing rule: {:?}", input);
-    let matched = RE_RULE.captures(input).unwrap();
+/// A rule is a field name followed by any number of `low-high` ranges joined by `or`
+fn parse_rule(input: &str) -> IResult<&str, Rule> {
+    let (input, (name, ranges)) = separated_pair(
+        take_while1(|c| c != ':'),
+        tag(": "),
+        separated_list1(tag(" or "), range),
+    )(input)?;
 
-    let range1_low = matched
-        .name("range1_low")
-        .unwrap()
-        .as_str()
-        .parse::<usize>()
-        .unwrap();
-    let range1_high = matched
-        .name("range1_high")
-        .unwrap()
-        .as_str()
-        .parse::<usize>()
-        .unwrap();
-    let range2_low = matched
-        .name("range2_low")
-        .unwrap()
-        .as_str()
-        .parse::<usize>()
-        .unwrap();
-    let range2_high = matched
-        .name("range2_high")
-        .unwrap()
-        .as_str()
-        .parse::<usize>()
-        .unwrap();
-
-    Rule {
-        name: matched.name("field").unwrap().as_str().to_owned(),
-        range1: (range1_low, range1_high),
-        range2: (range2_low, range2_high),
-    }
+    Ok((
+        input,
+        Rule {
+            name: name.to_owned(),
+            ranges: RangeSet::from_ranges(ranges),
+        },
+    ))
 }
 
 #[test]
@@ -179,58 +158,46 @@ fn test_parse_rule() {
     assert_eq!(
         Rule {
             name: "class".to_string(),
-            range1: (1, 3),
-            range2: (5, 7),
+            ranges: RangeSet::from_ranges(vec![(1, 3), (5, 7)]),
+        },
+        parse_line("class: 1-3 or 5-7", parse_rule).unwrap()
+    );
+    assert_eq!(
+        Rule {
+            name: "arrival track".to_string(),
+            ranges: RangeSet::from_ranges(vec![(1, 3), (5, 9), (20, 20)]),
         },
-        parse_rule("class: 1-3 or 5-7")
+        parse_line("arrival track: 5-7 or 1-3 or 20-20 or 6-9", parse_rule).unwrap()
     );
+
+    let error = parse_line("class: 1-3 or five-7", parse_rule).unwrap_err();
+    assert_eq!(11, error.column);
+    assert!(parse_line("class 1-3", parse_rule).is_err());
 }
 
 fn parse_data(input: &[String]) -> (Vec<Rule>, Vec<usize>, Vec<Vec<usize>>) {
-    let mut iter = input.iter();
-
-    let rules: Vec<Rule> = iter
-        .by_ref()
-        .take_while(|l| !l.is_empty())
-        .cloned()
-        .map(|l| parse_rule(&l))
-        .collect();
-
+    let sections = blocks(input);
     assert_eq!(
-        "your ticket:",
-        iter.next().unwrap(),
-        "Should be my ticket next"
+        3,
+        sections.len(),
+        "Should be rules, my ticket then nearby tickets"
     );
 
-    // My ticket only one line
-    let my_ticket = iter
-        .next()
-        .unwrap()
-        .split(',')
-        .map(|f| f.parse::<usize>().unwrap())
-        .collect::<Vec<usize>>();
+    let rules = parse_lines(sections[0], parse_rule).unwrap_or_else(|e| panic!("{}", e));
 
-    assert_eq!(
-        "",
-        iter.next().unwrap(),
-        "Line after my ticket should be blank"
-    );
+    let ticket = |line: &String| -> Vec<usize> {
+        parse_line(line, comma_separated(unsigned)).unwrap_or_else(|e| panic!("{}", e))
+    };
+
+    assert_eq!("your ticket:", sections[1][0], "Should be my ticket next");
+    // My ticket only one line
+    let my_ticket = ticket(&sections[1][1]);
 
     assert_eq!(
-        "nearby tickets:",
-        iter.next().unwrap(),
+        "nearby tickets:", sections[2][0],
         "Expect nearby tickets next"
     );
-
-    let nearby_tickets: Vec<Vec<usize>> = iter
-        .by_ref()
-        .cloned()
-        .map(|l| {
-            l.split(',')
-                .map(|f| f.parse::<usize>().unwrap())
-                .collect::<Vec<usize>>()
-        })
-        .collect();
+    let nearby_tickets: Vec<Vec<usize>> = sections[2][1..].iter().map(ticket).collect();
 
     (rules, my_ticket, nearby_tickets)
 }
@@ -238,38 +205,16 @@ fn parse_data(input: &[String]) -> (Vec<Rule>, Vec<usize>, Vec<Vec<usize>>) {
 pub fn solve_part_one(input: &[String]) -> usize {
     let (rules, _, nearby_tickets) = parse_data(input);
 
-    nearby_tickets.iter().fold(0, |acc, ticket| {
-        let invalid_fields: usize = ticket
-            .iter()
-            .filter(|&field| {
-                !rules
-                    .iter()
-                    .fold(false, |result, rule| result | rule.validate(field))
-            })
-            .sum();
-        acc + invalid_fields
-    })
-}
+    // A value is only invalid if no rule at all allows it
+    let any_rule = rules
+        .iter()
+        .fold(RangeSet::new(), |acc, rule| acc.union(&rule.ranges));
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        71,
-        solve_part_one(&[
-            "class: 1-3 or 5-7".to_string(),
-            "row: 6-11 or 33-44".to_string(),
-            "seat: 13-40 or 45-50".to_string(),
-            "".to_string(),
-            "your ticket:".to_string(),
-            "7,1,14".to_string(),
-            "".to_string(),
-            "nearby tickets:".to_string(),
-            "7,3,47".to_string(),
-            "40,4,50".to_string(),
-            "55,2,20".to_string(),
-            "38,6,12".to_string(),
-        ])
-    );
+    nearby_tickets
+        .iter()
+        .flatten()
+        .filter(|&&field| !any_rule.contains(field as isize))
+        .sum()
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
@@ -298,24 +243,15 @@ pub fn solve_part_two(input: &[String]) -> usize {
                 .iter()
                 .all(|ticket| rule.validate(&ticket[field_index]));
 
-            println!("{} @ [{}] => {}", rule.name, field_index, all_ok);
             if all_ok {
                 fields_name_indexes.insert(rule.name.clone(), field_index);
             }
         });
     }
 
-    println!("fields_name_indexes: {:?}", fields_name_indexes);
-
     fields_name_indexes
-        .iter()
-        .map(|(rule_name, rule_idx)| {
-            println!(
-                "rule_name {}, rule_idx {}, value {}",
-                rule_name, rule_idx, my_ticket[*rule_idx]
-            );
-            my_ticket[*rule_idx]
-        })
+        .values()
+        .map(|rule_idx| my_ticket[*rule_idx])
         .product()
 }
 
@@ -338,3 +274,95 @@ fn examples_part_two() {
     //     ])
     // );
 }
+
+/// Ticket notes for the real puzzle's 20 fields, with `size` nearby tickets, about a quarter of
+/// which have a value that's invalid for every field
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    const FIELDS: [&str; 20] = [
+        "departure location",
+        "departure station",
+        "departure platform",
+        "departure track",
+        "departure date",
+        "departure time",
+        "arrival location",
+        "arrival station",
+        "arrival platform",
+        "arrival track",
+        "class",
+        "duration",
+        "price",
+        "route",
+        "row",
+        "seat",
+        "train",
+        "type",
+        "wagon",
+        "zone",
+    ];
+
+    let rules: Vec<Rule> = FIELDS
+        .iter()
+        .map(|name| {
+            let low = rng.range(25, 50);
+            let gap = rng.range(200, 600);
+            let second = (gap + rng.range(2, 20), rng.range(620, 974));
+            Rule {
+                name: name.to_string(),
+                ranges: RangeSet::from_ranges(vec![(low, gap), second]),
+            }
+        })
+        .collect();
+    let mut order: Vec<&Rule> = rules.iter().collect();
+    rng.shuffle(&mut order);
+
+    let valid_ticket = |rng: &mut Rng| -> Vec<usize> {
+        order
+            .iter()
+            .map(|rule| {
+                let (low, high) = *rng.choose(rule.ranges.ranges());
+                rng.range(low, high) as usize
+            })
+            .collect()
+    };
+    let format_ticket = |values: Vec<usize>| {
+        values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+
+    let mut lines: Vec<String> = rules
+        .iter()
+        .map(|r| {
+            let ranges: Vec<String> = r
+                .ranges
+                .ranges()
+                .iter()
+                .map(|(low, high)| format!("{}-{}", low, high))
+                .collect();
+            format!("{}: {}", r.name, ranges.join(" or "))
+        })
+        .collect();
+    lines.push(String::new());
+    lines.push("your ticket:".to_string());
+    lines.push(format_ticket(valid_ticket(rng)));
+    lines.push(String::new());
+    lines.push("nearby tickets:".to_string());
+    for _ in 0..size.max(1) {
+        let mut values = valid_ticket(rng);
+        if rng.below(4) == 0 {
+            // Every rule's ranges lie within 25-974
+            let invalid = if rng.below(2) == 0 {
+                rng.below(25)
+            } else {
+                rng.range(975, 999) as usize
+            };
+            values[rng.below(FIELDS.len())] = invalid;
+        }
+        lines.push(format_ticket(values));
+    }
+
+    lines
+}
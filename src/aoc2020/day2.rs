@@ -51,6 +51,7 @@
 /// How many passwords are valid according to the new interpretation of the policies?
 use lazy_static::lazy_static;
 use regex::Regex;
+use rng::Rng;
 
 lazy_static! {
     static ref RE_PASSWORD_RULE: Regex = Regex::new(r"^(\d+)-(\d+) ([a-z]): (.+)$").unwrap();
@@ -103,26 +104,19 @@ pub fn solve_part_two(input: &[String]) -> usize {
         .count()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        2,
-        solve_part_one(&[
-            "1-3 a: abcde".to_string(),
-            "1-3 b: cdefg".to_string(),
-            "2-9 c: ccccccccc".to_string(),
-        ])
-    );
-}
+/// `size` password policies and passwords, drawn from a handful of letters so the policy letter
+/// turns up often enough for either policy to pass or fail
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let letters: Vec<char> = "abcdefgh".chars().collect();
+
+    (0..size.max(1))
+        .map(|_| {
+            let len = rng.range(4, 16);
+            let low = rng.range(1, len - 1);
+            let high = rng.range(low + 1, len);
+            let password: String = (0..len).map(|_| *rng.choose(&letters)).collect();
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        1,
-        solve_part_two(&[
-            "1-3 a: abcde".to_string(),
-            "1-3 b: cdefg".to_string(),
-            "2-9 c: ccccccccc".to_string(),
-        ])
-    );
+            format!("{}-{} {}: {}", low, high, rng.choose(&letters), password)
+        })
+        .collect()
 }
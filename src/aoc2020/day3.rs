@@ -1,3 +1,6 @@
+use grid::Grid;
+use rng::Rng;
+
 /// --- Day 3: Toboggan Trajectory ---
 ///
 /// With the toboggan login problems resolved, you set off toward the airport. While travel by toboggan might be easy, it's certainly not safe: there's very minimal steering and the area is covered in trees. You'll need to see which angles will take you near the fewest trees.
@@ -72,23 +75,15 @@
 ///
 
 fn traverse_mountain(input: &[String], step_x: usize, step_y: usize) -> usize {
-    let tree_vec: Vec<Vec<bool>> = input
-        .iter()
-        .map(|s| s.chars().map(|c| c == '#').collect())
-        .collect();
-
-    let mut trees = 0;
-    let mut x = 0;
-    for y in (0..input.len()).step_by(step_y) {
-        let row = &tree_vec[y];
-        let x_wrapped = x % row.len();
-        if row[x_wrapped] {
-            trees += 1;
-        }
-        x += step_x;
-    }
+    // The pattern repeats to the right as far as needed, but the slope ends at the bottom
+    let trees = Grid::parse(input, |c| c == '#').wrapping(true, false);
 
-    trees
+    let start_tree = trees[(0, 0)] as usize;
+    start_tree
+        + trees
+            .ray(0, 0, (step_x as isize, step_y as isize))
+            .filter(|(_, tree)| **tree)
+            .count()
 }
 
 pub fn solve_part_one(input: &[String]) -> usize {
@@ -107,42 +102,13 @@ pub fn solve_part_two(input: &[String]) -> usize {
     trees_seen.iter().product()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        7,
-        solve_part_one(&[
-            "..##.......".to_string(),
-            "#...#...#..".to_string(),
-            ".#....#..#.".to_string(),
-            "..#.#...#.#".to_string(),
-            ".#...##..#.".to_string(),
-            "..#.##.....".to_string(),
-            ".#.#.#....#".to_string(),
-            ".#........#".to_string(),
-            "#.##...#...".to_string(),
-            "#...##....#".to_string(),
-            ".#..#...#.#".to_string(),
-        ])
-    );
-}
-
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        336,
-        solve_part_two(&[
-            "..##.......".to_string(),
-            "#...#...#..".to_string(),
-            ".#....#..#.".to_string(),
-            "..#.#...#.#".to_string(),
-            ".#...##..#.".to_string(),
-            "..#.##.....".to_string(),
-            ".#.#.#....#".to_string(),
-            ".#........#".to_string(),
-            "#.##...#...".to_string(),
-            "#...##....#".to_string(),
-            ".#..#...#.#".to_string(),
-        ])
-    );
+/// A map `size` rows tall, as wide as the real one, with about one square in five a tree
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (0..size.max(1))
+        .map(|_| {
+            (0..31)
+                .map(|_| if rng.below(5) == 0 { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
 }
@@ -134,6 +134,7 @@
 ///
 use lazy_static::lazy_static;
 use regex::Regex;
+use rng::Rng;
 
 lazy_static! {
     static ref RE_FIELD: Regex = Regex::new(r"^([a-z]+):(.+)$").unwrap();
@@ -331,64 +332,78 @@ pub fn solve_part_two(input: &[String]) -> usize {
     validate_passports(input, Passport::fancy_validation)
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        2,
-        solve_part_one(&[
-            "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd".to_string(),
-            "byr:1937 iyr:2017 cid:147 hgt:183cm".to_string(),
-            "".to_string(),
-            "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884".to_string(),
-            "hcl:#cfa07d byr:1929".to_string(),
-            "".to_string(),
-            "hcl:#ae17e1 iyr:2013".to_string(),
-            "eyr:2024".to_string(),
-            "ecl:brn pid:760753108 byr:1931".to_string(),
-            "hgt:179cm".to_string(),
-            "".to_string(),
-            "hcl:#cfa07d eyr:2025 pid:166559648".to_string(),
-            "iyr:2011 ecl:brn hgt:59in".to_string(),
-        ])
-    );
-}
+/// `size` passports in a batch file. Each field is independently valid, invalid or missing, and
+/// fields are spread over one to three lines in a random order.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut lines = vec![];
+
+    for passport in 0..size.max(1) {
+        let valid = rng.below(3) != 0;
+        let pick = |rng: &mut Rng, good: String, bad: String| match rng.below(10) {
+            0 => None,
+            1 => Some(bad),
+            _ if valid => Some(good),
+            _ => Some(bad),
+        };
+        let year = |rng: &mut Rng, low: isize, high: isize| rng.range(low, high).to_string();
+        let hex = |rng: &mut Rng, len: usize| -> String {
+            (0..len)
+                .map(|_| char::from(b"0123456789abcdef"[rng.below(16)]))
+                .collect()
+        };
+        let digits = |rng: &mut Rng, len: usize| -> String {
+            (0..len).map(|_| rng.below(10).to_string()).collect()
+        };
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        0,
-        solve_part_two(&[
-            "eyr:1972 cid:100".to_string(),
-            "hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926".to_string(),
-            "".to_string(),
-            "iyr:2019".to_string(),
-            "hcl:#602927 eyr:1967 hgt:170cm".to_string(),
-            "ecl:grn pid:012533040 byr:1946".to_string(),
-            "".to_string(),
-            "hcl:dab227 iyr:2012".to_string(),
-            "ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277".to_string(),
-            "".to_string(),
-            "hgt:59cm ecl:zzz".to_string(),
-            "eyr:2038 hcl:74454a iyr:2023".to_string(),
-            "pid:3556412378 byr:2007".to_string(),
-        ])
-    );
+        let byr = (year(rng, 1920, 2002), year(rng, 1900, 1919));
+        let iyr = (year(rng, 2010, 2020), year(rng, 2021, 2030));
+        let eyr = (year(rng, 2020, 2030), year(rng, 2000, 2019));
+        let hgt = match rng.below(2) {
+            0 => (
+                format!("{}cm", rng.range(150, 193)),
+                format!("{}in", rng.range(150, 193)),
+            ),
+            _ => (
+                format!("{}in", rng.range(59, 76)),
+                rng.range(59, 76).to_string(),
+            ),
+        };
+        let hcl = (format!("#{}", hex(rng, 6)), hex(rng, 6));
+        let ecl = (
+            rng.choose(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])
+                .to_string(),
+            rng.choose(&["zzz", "red", "xry"]).to_string(),
+        );
+        let pid = (digits(rng, 9), digits(rng, 10));
+        let cid = (rng.range(1, 350).to_string(), rng.range(1, 350).to_string());
+
+        let mut fields: Vec<String> = vec![
+            ("byr", byr),
+            ("iyr", iyr),
+            ("eyr", eyr),
+            ("hgt", hgt),
+            ("hcl", hcl),
+            ("ecl", ecl),
+            ("pid", pid),
+            ("cid", cid),
+        ]
+        .into_iter()
+        .filter_map(|(name, (good, bad))| {
+            pick(rng, good, bad).map(|value| format!("{}:{}", name, value))
+        })
+        .collect();
+        if fields.is_empty() {
+            fields.push(format!("cid:{}", rng.range(1, 350)));
+        }
+        rng.shuffle(&mut fields);
+
+        if passport > 0 {
+            lines.push(String::new());
+        }
+        let breaks = rng.range(0, 2) as usize;
+        let per_line = fields.len().div_ceil(breaks + 1).max(1);
+        lines.extend(fields.chunks(per_line).map(|chunk| chunk.join(" ")));
+    }
 
-    assert_eq!(
-        4,
-        solve_part_two(&[
-            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980".to_string(),
-            "hcl:#623a2f".to_string(),
-            "".to_string(),
-            "eyr:2029 ecl:blu cid:129 byr:1989".to_string(),
-            "iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm".to_string(),
-            "".to_string(),
-            "hcl:#888785".to_string(),
-            "hgt:164cm byr:2001 iyr:2015 cid:88".to_string(),
-            "pid:545766238 ecl:hzl".to_string(),
-            "eyr:2022".to_string(),
-            "".to_string(),
-            "iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719".to_string(),
-        ])
-    );
+    lines
 }
@@ -1,3 +1,6 @@
+use bits::decode;
+use rng::Rng;
+
 /// # Day 5: Binary Boarding
 ///
 /// You board your plane only to discover a new problem: you dropped your boarding pass! You aren't
@@ -94,28 +97,10 @@ fn binary_space_partition(input: &str, value_range: (usize, usize)) -> usize {
 /// 3 chars -> 0-7 matched 7b111_1111 == 127 and 3b111 == 3
 ///
 /// This means each "take the upper half" of a range can be converted to a binary 1 and each "take
-/// the lower half" can be a binary 0.
-fn simon_solution(input: &str) -> usize {
-    let result = input.chars().fold((0, 0), |(value, idx), c| {
-        (
-            value << 1
-                | match c {
-                    'F' | 'L' => 0,
-                    'B' | 'R' => 1,
-                    _ => panic!("Invalid char: {}", c),
-                },
-            idx + 1,
-        )
-    });
-
-    result.0
-}
-
+/// the lower half" can be a binary 0. Multiplying the row by 8 shifts it up past the column's three
+/// bits, so the seat ID is just the whole boarding pass read as one binary number.
 fn calculate_seat_id(input: &str) -> usize {
-    let row = simon_solution(&input[0..7]);
-    let col = simon_solution(&input[7..10]);
-
-    row * 8 + col
+    decode(input, "FL", "BR").unwrap_or_else(|| panic!("Invalid boarding pass: {}", input)) as usize
 }
 
 pub fn solve_part_one(input: &[String]) -> usize {
@@ -160,52 +145,135 @@ fn test_col_parse() {
 }
 
 #[test]
-fn test_simon_solution_same_as_naive_bsp() {
-    assert_eq!(
-        binary_space_partition("FBFBBFF", (0, 127)),
-        simon_solution("FBFBBFF")
-    );
-    assert_eq!(
-        binary_space_partition("BFFFBBF", (0, 127)),
-        simon_solution("BFFFBBF")
-    );
-    assert_eq!(
-        binary_space_partition("FFFBBBF", (0, 127)),
-        simon_solution("FFFBBBF")
-    );
-    assert_eq!(
-        binary_space_partition("BBFFBBF", (0, 127)),
-        simon_solution("BBFFBBF")
-    );
-    assert_eq!(binary_space_partition("RLR", (0, 7)), simon_solution("RLR"));
-    assert_eq!(binary_space_partition("RRR", (0, 7)), simon_solution("RRR"));
-    assert_eq!(binary_space_partition("RRR", (0, 7)), simon_solution("RRR"));
-    assert_eq!(binary_space_partition("RLL", (0, 7)), simon_solution("RLL"));
+fn test_decoding_same_as_naive_bsp() {
+    for row in &["FBFBBFF", "BFFFBBF", "FFFBBBF", "BBFFBBF"] {
+        assert_eq!(
+            binary_space_partition(row, (0, 127)) as u64,
+            decode(row, "F", "B").unwrap()
+        );
+    }
+    for col in &["RLR", "RRR", "RLL"] {
+        assert_eq!(
+            binary_space_partition(col, (0, 7)) as u64,
+            decode(col, "L", "R").unwrap()
+        );
+    }
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(567, solve_part_one(&["BFFFBBFRRR".to_string()]));
-    assert_eq!(119, solve_part_one(&["FFFBBBFRRR".to_string()]));
-    assert_eq!(820, solve_part_one(&["BBFFBBFRLL".to_string()]));
-    assert_eq!(
-        820,
-        solve_part_one(&[
-            "BFFFBBFRRR".to_string(),
-            "FFFBBBFRRR".to_string(),
-            "BBFFBBFRLL".to_string(),
-        ])
-    );
+/// Turn a seat ID back in to the boarding pass that describes it
+fn encode_boarding_pass(seat_id: usize) -> String {
+    (0..10)
+        .map(|bit| match (bit < 7, seat_id >> (9 - bit) & 1 == 1) {
+            (true, false) => 'F',
+            (true, true) => 'B',
+            (false, false) => 'L',
+            (false, true) => 'R',
+        })
+        .collect()
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        120,
-        solve_part_two(&[
-            "BFFFBBFRRR".to_string(),
-            "FFFBBBFRRR".to_string(),
-            "BBFFBBFRLL".to_string(),
-        ])
-    );
+/// A shuffled flight's worth of `size` boarding passes (at most 1022) for a run of seats, with
+/// exactly one seat in the middle of the run missing
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let run = size.clamp(2, 1022) + 1;
+    let first = rng.below(1025 - run);
+    let last = first + run - 1;
+    let missing = rng.range(first as isize + 1, last as isize - 1) as usize;
+
+    let mut passes: Vec<String> = (first..=last)
+        .filter(|seat_id| *seat_id != missing)
+        .map(encode_boarding_pass)
+        .collect();
+    rng.shuffle(&mut passes);
+
+    passes
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use property;
+    use std::collections::HashSet;
+
+    /// A generated input of random size
+    fn any_input(rng: &mut Rng) -> Vec<String> {
+        let size = rng.below(1000) + 2;
+        generate(rng, size)
+    }
+
+    fn bsp_seat_id(boarding_pass: &str) -> usize {
+        binary_space_partition(&boarding_pass[0..7], (0, 127)) * 8
+            + binary_space_partition(&boarding_pass[7..10], (0, 7))
+    }
+
+    #[test]
+    fn property_decoding_same_as_naive_bsp() {
+        property::check(
+            "decoding as binary agrees with binary_space_partition",
+            any_input,
+            |passes| {
+                for pass in passes {
+                    let (row, col) = pass.split_at(7);
+                    let naive = (
+                        binary_space_partition(row, (0, 127)),
+                        binary_space_partition(col, (0, 7)),
+                    );
+                    let decoded = (
+                        decode(row, "F", "B").unwrap() as usize,
+                        decode(col, "L", "R").unwrap() as usize,
+                    );
+                    if naive != decoded {
+                        return Err(format!(
+                            "{}: naive {:?}, decoded {:?}",
+                            pass, naive, decoded
+                        ));
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn property_part_one_is_highest_seat() {
+        property::check("part one is the highest seat ID", any_input, |passes| {
+            let highest = solve_part_one(passes);
+            match passes.iter().map(|p| bsp_seat_id(p)).max() {
+                Some(expected) if expected != highest => {
+                    Err(format!("expected {}, got {}", expected, highest))
+                }
+                _ => Ok(()),
+            }
+        });
+    }
+
+    #[test]
+    fn property_part_two_finds_gap_between_neighbours() {
+        property::check(
+            "part two finds a seat whose neighbours are both taken",
+            any_input,
+            |passes| {
+                let seat_ids: HashSet<usize> = passes.iter().map(|p| bsp_seat_id(p)).collect();
+                let has_gap = seat_ids
+                    .iter()
+                    .any(|id| !seat_ids.contains(&(id + 1)) && seat_ids.contains(&(id + 2)));
+                if !has_gap {
+                    return Ok(());
+                }
+
+                let seat = solve_part_two(passes);
+                if seat_ids.contains(&seat)
+                    || !seat_ids.contains(&(seat - 1))
+                    || !seat_ids.contains(&(seat + 1))
+                {
+                    Err(format!(
+                        "{} is not an empty seat between two taken ones",
+                        seat
+                    ))
+                } else {
+                    Ok(())
+                }
+            },
+        );
+    }
 }
@@ -1,4 +1,14 @@
-use std::collections::{HashMap, HashSet};
+use counter::Counter;
+use parse::blocks;
+use rng::Rng;
+
+/// How many people in each group answered yes to each question
+fn group_answers(input: &[String]) -> Vec<(usize, Counter<char>)> {
+    blocks(input)
+        .iter()
+        .map(|group| (group.len(), group.iter().flat_map(|l| l.chars()).collect()))
+        .collect()
+}
 
 /// --- Day 6: Custom Customs ---
 ///
@@ -93,102 +103,49 @@ use std::collections::{HashMap, HashSet};
 /// For each group, count the number of questions to which everyone answered "yes". What is the sum of those counts?
 
 pub fn solve_part_one(input: &[String]) -> usize {
-    let mut answers_counts = Vec::new();
-    let mut answers = HashSet::new();
-
-    for line in input {
-        if line.is_empty() {
-            answers_counts.push(answers.len());
-            answers.clear();
-        } else {
-            line.chars().for_each(|c| {
-                answers.insert(c);
-            });
-        }
-    }
-    answers_counts.push(answers.len());
-
-    answers_counts.iter().sum()
+    group_answers(input)
+        .iter()
+        .map(|(_, answers)| answers.len())
+        .sum()
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
-    let mut answers_counts = Vec::new();
+    group_answers(input)
+        .iter()
+        .map(|(people, answers)| answers.with_count(*people).count())
+        .sum()
+}
 
-    let mut answers: HashMap<char, usize> = HashMap::new();
-    let mut group_people_count: usize = 0;
+/// `size` groups of one to five people. People in a group draw their answers mostly from a set of
+/// questions the group has in common, so some questions are answered by everyone.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let question = |rng: &mut Rng| char::from(b'a' + rng.below(26) as u8);
+    let mut lines = vec![];
 
-    for line in input {
-        if line.is_empty() {
-            answers_counts.push(
-                answers
-                    .values()
-                    .filter(|&v| v == &group_people_count)
-                    .count(),
-            );
-            answers.clear();
-            group_people_count = 0;
-        } else {
-            line.chars().for_each(|c| {
-                let next_count = answers.get(&c).map(|v| v + 1).unwrap_or(1);
-                answers.insert(c, next_count);
-            });
-            group_people_count += 1;
+    for group in 0..size.max(1) {
+        if group > 0 {
+            lines.push(String::new());
         }
-    }
-    answers_counts.push(
-        answers
-            .values()
-            .filter(|&v| v == &group_people_count)
-            .count(),
-    );
-
-    answers_counts.iter().sum()
-}
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        11,
-        solve_part_one(&[
-            "abc".to_string(),
-            "".to_string(),
-            "a".to_string(),
-            "b".to_string(),
-            "c".to_string(),
-            "".to_string(),
-            "ab".to_string(),
-            "ac".to_string(),
-            "".to_string(),
-            "a".to_string(),
-            "a".to_string(),
-            "a".to_string(),
-            "a".to_string(),
-            "".to_string(),
-            "b".to_string(),
-        ])
-    );
-}
+        let common: Vec<char> = (0..rng.range(1, 10)).map(|_| question(rng)).collect();
+        for _ in 0..rng.range(1, 5) {
+            let mut answers: Vec<char> = common
+                .iter()
+                .filter(|_| rng.below(4) != 0)
+                .cloned()
+                .collect();
+            for _ in 0..rng.below(4) {
+                answers.push(question(rng));
+            }
+            answers.sort_unstable();
+            answers.dedup();
+            if answers.is_empty() {
+                answers.push(common[0]);
+            }
+            rng.shuffle(&mut answers);
+            lines.push(answers.iter().collect());
+        }
+    }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        6,
-        solve_part_two(&[
-            "abc".to_string(),
-            "".to_string(),
-            "a".to_string(),
-            "b".to_string(),
-            "c".to_string(),
-            "".to_string(),
-            "ab".to_string(),
-            "ac".to_string(),
-            "".to_string(),
-            "a".to_string(),
-            "a".to_string(),
-            "a".to_string(),
-            "a".to_string(),
-            "".to_string(),
-            "b".to_string(),
-        ])
-    );
+    lines
 }
@@ -79,13 +79,14 @@
 /// In this example, a single shiny gold bag must contain 126 other bags.
 ///
 /// How many individual bags are required inside your single shiny gold bag?
+use graph::Graph;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take_until};
-use nom::character::complete::digit1;
 use nom::combinator::{map, opt};
-use nom::lib::std::collections::{HashMap, HashSet};
 use nom::multi::many0;
 use nom::IResult;
+use parse::unsigned;
+use rng::Rng;
 
 #[derive(Debug, PartialEq)]
 struct ChildRule {
@@ -99,13 +100,8 @@ struct Rule {
     children: Vec<ChildRule>,
 }
 
-fn number(input: &str) -> IResult<&str, usize> {
-    let (input, count) = map(digit1, std::str::FromStr::from_str)(input)?;
-    Ok((input, count.unwrap()))
-}
-
 fn parse_child_rule(input: &str) -> IResult<&str, ChildRule> {
-    let (input, count) = number(input)?;
+    let (input, count) = unsigned(input)?;
     let (input, _) = tag(" ")(input)?;
     let (input, bag_name) = take_until(" bag")(input)?;
     let (input, _) = tag(" bag")(input)?;
@@ -140,85 +136,40 @@ fn parse_rule(input: &str) -> IResult<&str, Rule> {
     ))
 }
 
-pub fn solve_part_one(input: &[String]) -> usize {
-    // Parse all the rules
-    let rules: Vec<Rule> = input
+/// Bags pointing at the bags they directly contain, weighted by how many of each
+fn parse_bags(input: &[String]) -> Graph<usize> {
+    let mut bags = Graph::new();
+    input
         .iter()
         .map(|i| parse_rule(i).map(|(_, rule)| rule).unwrap())
-        .collect();
-
-    // Turn rules into bag contained-by tree
-    let mut contained_by: HashMap<&str, Vec<&str>> = HashMap::new();
-    rules.iter().for_each(|r| {
-        let container_bag = &r.bag_name.as_str();
-        r.children.iter().for_each(|c| {
-            match contained_by.get_mut(c.bag_name.as_str()) {
-                None => {
-                    contained_by.insert(c.bag_name.as_str(), vec![container_bag]);
-                }
-                Some(cc) => cc.push(container_bag),
-            };
+        .for_each(|rule| {
+            bags.node(&rule.bag_name);
+            rule.children
+                .iter()
+                .for_each(|c| bags.add_edge(&rule.bag_name, &c.bag_name, c.count));
         });
-    });
-
-    let our_bag = "shiny gold";
-
-    let mut container_bags: HashSet<&str> = HashSet::new();
-
-    let mut parent_bags = vec![our_bag];
+    bags
+}
 
-    while !parent_bags.is_empty() {
-        if let Some(new_parents) = contained_by.get(parent_bags.pop().unwrap()) {
-            new_parents.iter().for_each(|b| {
-                container_bags.insert(b);
-                parent_bags.push(b);
-            });
-        };
-    }
+pub fn solve_part_one(input: &[String]) -> usize {
+    // Flip the bags round to find everything our bag can end up inside
+    let contained_by = parse_bags(input).reversed();
+    let our_bag = contained_by.id("shiny gold").unwrap();
 
-    container_bags.len()
+    contained_by.bfs(our_bag).len() - 1
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
-    // Parse all the rules
-    let rules: Vec<Rule> = input
-        .iter()
-        .map(|i| parse_rule(i).map(|(_, rule)| rule).unwrap())
-        .collect();
-
-    // Turn rules into bag contains tree
-    let mut bag_rule_map: HashMap<&str, &Rule> = HashMap::new();
-    let mut bag_contain_count_map: HashMap<&str, usize> = HashMap::new();
-    rules.iter().for_each(|r| {
-        bag_rule_map.insert(r.bag_name.as_str(), r);
-        bag_contain_count_map.insert(
-            r.bag_name.as_str(),
-            r.children.iter().map(|c| c.count).sum(),
-        );
-    });
+    let bags = parse_bags(input);
+    let our_bag = bags.id("shiny gold").unwrap();
 
-    let our_bag = "shiny gold";
-
-    let mut contained_bag_count = 0;
-
-    let mut parent_bags: Vec<(usize, &str)> = vec![(1, our_bag)];
-    while let Some((multiplier, parent_name)) = parent_bags.pop() {
-        let size = bag_contain_count_map.get(parent_name).unwrap();
-        // println!("{}: {} x {}", parent_name, multiplier, size);
-
-        contained_bag_count += multiplier * size;
-
-        bag_rule_map
-            .get(parent_name)
-            .unwrap()
-            .children
+    // Every bag inside counts once itself along with everything inside it
+    bags.aggregate(our_bag, |_, inside| {
+        inside
             .iter()
-            .for_each(|c| {
-                parent_bags.push((multiplier * c.count, c.bag_name.as_str()));
-            });
-    }
-
-    contained_bag_count
+            .map(|(count, contents)| *count * (1 + contents))
+            .sum()
+    })
 }
 
 #[test]
@@ -254,50 +205,93 @@ fn test_parse_rule() {
     );
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        4,
-        solve_part_one(&[
-            "light red bags contain 1 bright white bag, 2 muted yellow bags.".to_string(),
-            "dark orange bags contain 3 bright white bags, 4 muted yellow bags.".to_string(),
-            "bright white bags contain 1 shiny gold bag.".to_string(),
-            "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.".to_string(),
-            "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.".to_string(),
-            "dark olive bags contain 3 faded blue bags, 4 dotted black bags.".to_string(),
-            "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.".to_string(),
-            "faded blue bags contain no other bags.".to_string(),
-            "dotted black bags contain no other bags.".to_string(),
-        ])
-    );
-}
+/// Rules for `size` bags (up to 600), including a shiny gold bag with no more than ten different
+/// bags inside it
+///
+/// Bags are generated in an order where bags only ever contain bags that come later, each bag
+/// being put inside one or two earlier ones, so rules never go round in circles.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    const ADJECTIVES: [&str; 20] = [
+        "light", "dark", "bright", "muted", "shiny", "faded", "dotted", "vibrant", "dull", "pale",
+        "posh", "mirrored", "wavy", "plaid", "striped", "drab", "clear", "dim", "vivid", "dusky",
+    ];
+    const COLOURS: [&str; 30] = [
+        "red",
+        "orange",
+        "white",
+        "yellow",
+        "gold",
+        "olive",
+        "plum",
+        "black",
+        "blue",
+        "green",
+        "teal",
+        "maroon",
+        "silver",
+        "aqua",
+        "beige",
+        "bronze",
+        "brown",
+        "chartreuse",
+        "coral",
+        "crimson",
+        "cyan",
+        "fuchsia",
+        "gray",
+        "indigo",
+        "lavender",
+        "lime",
+        "magenta",
+        "purple",
+        "salmon",
+        "tan",
+    ];
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        32,
-        solve_part_two(&[
-            "light red bags contain 1 bright white bag, 2 muted yellow bags.".to_string(),
-            "dark orange bags contain 3 bright white bags, 4 muted yellow bags.".to_string(),
-            "bright white bags contain 1 shiny gold bag.".to_string(),
-            "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.".to_string(),
-            "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.".to_string(),
-            "dark olive bags contain 3 faded blue bags, 4 dotted black bags.".to_string(),
-            "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.".to_string(),
-            "faded blue bags contain no other bags.".to_string(),
-            "dotted black bags contain no other bags.".to_string(),
-        ])
-    );
-    assert_eq!(
-        126,
-        solve_part_two(&[
-            "shiny gold bags contain 2 dark red bags.".to_string(),
-            "dark red bags contain 2 dark orange bags.".to_string(),
-            "dark orange bags contain 2 dark yellow bags.".to_string(),
-            "dark yellow bags contain 2 dark green bags.".to_string(),
-            "dark green bags contain 2 dark blue bags.".to_string(),
-            "dark blue bags contain 2 dark violet bags.".to_string(),
-            "dark violet bags contain no other bags.".to_string(),
-        ])
-    );
+    let mut names: Vec<String> = ADJECTIVES
+        .iter()
+        .flat_map(|a| COLOURS.iter().map(move |c| format!("{} {}", a, c)))
+        .filter(|name| name != "shiny gold")
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(size.clamp(2, 600) - 1);
+    let shiny_gold = names.len().saturating_sub(10);
+    names.insert(shiny_gold, "shiny gold".to_string());
+
+    let mut contents: Vec<Vec<(isize, usize)>> = vec![vec![]; names.len()];
+    for bag in 1..names.len() {
+        for _ in 0..rng.range(1, 2) {
+            // Make sure shiny gold always has something inside it for part two to count
+            let container = match bag == shiny_gold + 1 {
+                true if contents[shiny_gold].is_empty() => shiny_gold,
+                _ => rng.below(bag),
+            };
+            if !contents[container].iter().any(|(_, b)| *b == bag) {
+                contents[container].push((rng.range(1, 5), bag));
+            }
+        }
+    }
+
+    let mut rules: Vec<String> = names
+        .iter()
+        .zip(contents.iter())
+        .map(|(name, inside)| match inside.len() {
+            0 => format!("{} bags contain no other bags.", name),
+            _ => format!(
+                "{} bags contain {}.",
+                name,
+                inside
+                    .iter()
+                    .map(|(count, bag)| match count {
+                        1 => format!("1 {} bag", names[*bag]),
+                        _ => format!("{} {} bags", count, names[*bag]),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        })
+        .collect();
+    rng.shuffle(&mut rules);
+
+    rules
 }
@@ -1,11 +1,11 @@
-use nom::branch::alt;
 use nom::bytes::complete::take_until;
-use nom::character::complete::{char, digit1};
-use nom::lib::std::collections::HashSet;
+use nom::character::complete::char;
 use nom::IResult;
 
 use aoc2020::day8::INSTRUCTION::{ACC, JMP, NOP};
-use std::ops::Neg;
+use cycle::Detector;
+use parse::{parse_lines, signed};
+use rng::Rng;
 
 /// --- Day 8: Handheld Halting ---
 ///
@@ -136,28 +136,10 @@ enum INSTRUCTION {
     JMP(isize),
 }
 
-pub fn parse_int(input: &str) -> IResult<&str, isize> {
-    let (input, sign) = alt((char('-'), char('+')))(input)?;
-    let (input, digits) = digit1(input)?;
-
-    let num: isize = digits.parse().unwrap();
-
-    Ok((
-        input,
-        match sign {
-            '+' => num,
-            '-' => num.neg(),
-            _ => {
-                panic!("wut")
-            }
-        },
-    ))
-}
-
 fn parse_instruction(input: &str) -> IResult<&str, INSTRUCTION> {
     let (input, instruction) = take_until(" ")(input)?;
     let (input, _) = char(' ')(input)?;
-    let (input, arg) = parse_int(input)?;
+    let (input, arg) = signed(input)?;
 
     let instr = match instruction {
         "nop" => NOP(arg),
@@ -205,9 +187,9 @@ impl GameConsole {
     pub fn run(&mut self) -> (bool, Vec<(usize, INSTRUCTION)>) {
         let mut potential_patches = Vec::new();
 
-        let mut seen_program_counters: HashSet<usize> = HashSet::new();
-        while !seen_program_counters.contains(&self.pc) && self.pc != self.rom.len() {
-            seen_program_counters.insert(self.pc);
+        // The console only loops once it gets back to an instruction it's already run
+        let mut seen_program_counters = Detector::new();
+        while self.pc != self.rom.len() && seen_program_counters.observe(self.pc).is_none() {
             let instr = &self.rom[self.pc];
             // println!(
             //     "{:?} :: {:?}",
@@ -244,10 +226,7 @@ impl GameConsole {
 }
 
 pub fn solve_part_one(input: &[String]) -> isize {
-    let instructions: Vec<INSTRUCTION> = input
-        .iter()
-        .map(|i| parse_instruction(i).map(|(_, instr)| instr).unwrap())
-        .collect();
+    let instructions = parse_lines(input, parse_instruction).unwrap();
 
     let mut console = GameConsole::new(instructions);
     console.run();
@@ -256,10 +235,7 @@ pub fn solve_part_one(input: &[String]) -> isize {
 }
 
 pub fn solve_part_two(input: &[String]) -> isize {
-    let instructions: Vec<INSTRUCTION> = input
-        .iter()
-        .map(|i| parse_instruction(i).map(|(_, instr)| instr).unwrap())
-        .collect();
+    let instructions = parse_lines(input, parse_instruction).unwrap();
 
     let mut console = GameConsole::new(instructions);
     let (finished_successfully, potential_patches) = console.run();
@@ -284,12 +260,6 @@ pub fn solve_part_two(input: &[String]) -> isize {
     panic!("Could not find a patch that got the boot rom to succeed");
 }
 
-#[test]
-fn test_parse_isize() {
-    assert_eq!(Ok(("", 123)), parse_int("+123"));
-    assert_eq!(Ok(("", -123)), parse_int("-123"));
-}
-
 #[test]
 fn test_parse_instr() {
     assert_eq!(Ok(("", NOP(0))), parse_instruction("nop +0"));
@@ -297,38 +267,164 @@ fn test_parse_instr() {
     assert_eq!(Ok(("", JMP(456))), parse_instruction("jmp +456"));
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        5,
-        solve_part_one(&[
-            "nop +0".to_string(),
-            "acc +1".to_string(),
-            "jmp +4".to_string(),
-            "acc +3".to_string(),
-            "jmp -3".to_string(),
-            "acc -99".to_string(),
-            "acc +1".to_string(),
-            "jmp -4".to_string(),
-            "acc +6".to_string(),
-        ])
-    );
+fn format_instruction(instruction: &INSTRUCTION) -> String {
+    match instruction {
+        NOP(arg) => format!("nop {:+}", arg),
+        ACC(arg) => format!("acc {:+}", arg),
+        JMP(arg) => format!("jmp {:+}", arg),
+    }
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        8,
-        solve_part_two(&[
-            "nop +0".to_string(),
-            "acc +1".to_string(),
-            "jmp +4".to_string(),
-            "acc +3".to_string(),
-            "jmp -3".to_string(),
-            "acc -99".to_string(),
-            "acc +1".to_string(),
-            "jmp -4".to_string(),
-            "acc +6".to_string(),
-        ])
-    );
+/// A boot program of `size` instructions that loops forever, but which a single `jmp` -> `nop`
+/// patch fixes
+///
+/// Built by generating a program that only ever jumps forwards (so always terminates) and then
+/// replacing one instruction on its execution path with a backwards jump.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let len = size.max(1);
+    let mut program: Vec<INSTRUCTION> = (0..len as isize)
+        .map(|pc| match rng.below(3) {
+            0 => ACC(rng.range(-99, 99)),
+            // Keep nop targets in bounds so patching them to a jmp is still a valid program
+            1 => NOP(rng.range(-pc, len as isize - pc)),
+            _ => JMP(rng.range(1, len as isize - pc)),
+        })
+        .collect();
+
+    let mut executed = vec![];
+    let mut pc = 0;
+    while pc < len {
+        executed.push(pc);
+        pc = match program[pc] {
+            JMP(arg) => pc + arg as usize,
+            _ => pc + 1,
+        };
+    }
+    // Jump back to somewhere already executed, so the program is sure to end up here again
+    let looping = rng.below(executed.len());
+    let looping_pc = executed[looping];
+    let target_pc = *rng.choose(&executed[..=looping]);
+    program[looping_pc] = JMP(target_pc as isize - looping_pc as isize);
+
+    program.iter().map(format_instruction).collect()
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use property;
+
+    /// A generated input of random size
+    fn any_input(rng: &mut Rng) -> Vec<String> {
+        let size = rng.below(40) + 1;
+        generate(rng, size)
+    }
+
+    /// A deliberately simple interpreter to check the `GameConsole` against, returning whether the
+    /// program terminated and the accumulator when it stopped
+    fn reference_run(program: &[INSTRUCTION]) -> (bool, isize) {
+        let mut visited = vec![false; program.len()];
+        let (mut pc, mut acc) = (0isize, 0);
+        while pc >= 0 && (pc as usize) < program.len() && !visited[pc as usize] {
+            visited[pc as usize] = true;
+            match program[pc as usize] {
+                NOP(_) => pc += 1,
+                ACC(arg) => {
+                    acc += arg;
+                    pc += 1
+                }
+                JMP(arg) => pc += arg,
+            }
+        }
+
+        (pc as usize == program.len(), acc)
+    }
+
+    fn parse_program(input: &[String]) -> Vec<INSTRUCTION> {
+        input
+            .iter()
+            .map(|i| parse_instruction(i).unwrap().1)
+            .collect()
+    }
+
+    /// Every jump, and every nop if patched to a jump, lands inside the program or just past its end
+    fn all_targets_in_bounds(program: &[INSTRUCTION]) -> bool {
+        program.iter().enumerate().all(|(pc, i)| match i {
+            NOP(arg) | JMP(arg) => (0..=program.len() as isize).contains(&(pc as isize + arg)),
+            ACC(_) => true,
+        })
+    }
+
+    #[test]
+    fn property_instructions_round_trip() {
+        property::check(
+            "formatting then parsing an instruction gives it back",
+            any_input,
+            |input| {
+                for line in input {
+                    let instruction = parse_instruction(line).unwrap().1;
+                    if &format_instruction(&instruction) != line {
+                        return Err(format!("{} became {:?}", line, instruction));
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn property_part_one_matches_reference() {
+        property::check(
+            "part one matches the reference interpreter",
+            any_input,
+            |input| {
+                let program = parse_program(input);
+                if !all_targets_in_bounds(&program) {
+                    return Ok(());
+                }
+
+                let (_, expected) = reference_run(&program);
+                let actual = solve_part_one(input);
+                if expected == actual {
+                    Ok(())
+                } else {
+                    Err(format!("expected {}, got {}", expected, actual))
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn property_part_two_comes_from_a_single_patch() {
+        property::check(
+            "part two's answer is reachable by patching one instruction",
+            any_input,
+            |input| {
+                let program = parse_program(input);
+                let patches: Vec<Vec<INSTRUCTION>> = (0..program.len())
+                    .filter_map(|pc| {
+                        let patched = match program[pc] {
+                            NOP(arg) => JMP(arg),
+                            JMP(arg) => NOP(arg),
+                            ACC(_) => return None,
+                        };
+                        let mut patched_program = program.clone();
+                        patched_program[pc] = patched;
+                        Some(patched_program)
+                    })
+                    .collect();
+                let fixable = patches.iter().any(|p| reference_run(p).0);
+                if !all_targets_in_bounds(&program) || reference_run(&program).0 || !fixable {
+                    return Ok(());
+                }
+
+                let acc = solve_part_two(input);
+                if patches.iter().any(|p| reference_run(p) == (true, acc)) {
+                    Ok(())
+                } else {
+                    Err(format!("no single patch terminates with acc {}", acc))
+                }
+            },
+        );
+    }
 }
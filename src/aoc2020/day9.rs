@@ -1,4 +1,5 @@
-use nom::lib::std::cmp::Ordering;
+use rng::Rng;
+use target_sum::{contiguous_sums_non_negative, PairSums};
 
 /// --- Day 9: Encoding Error ---
 ///
@@ -109,94 +110,87 @@ use nom::lib::std::cmp::Ordering;
 /// What is the encryption weakness in your XMAS-encrypted list of numbers?
 ///
 
-fn parse_input(input: &[String]) -> Vec<usize> {
+fn parse_input(input: &[String]) -> Vec<isize> {
     input
         .iter()
-        .map(|c| c.trim().parse::<usize>().unwrap())
+        .map(|c| c.trim().parse::<isize>().unwrap())
         .collect()
 }
 
-fn part_one_solver(numbers: &[usize], preamble_size: usize) -> usize {
-    for idx in preamble_size..numbers.len() {
-        let val = numbers[idx];
-        let mut is_valid = false;
-        // Bjørn, in register 0, is fat and lazy. He ambles along like his namesake.
-        let lookback_start = idx - preamble_size;
-        for bjorn in lookback_start..idx {
-            // One will be the fast iterator. Her name is Jorunn, and her legs are strong from years of skiing. She flies forward with powerful strokes.
-            for jorunn in bjorn..idx {
-                let number_a = numbers[bjorn];
-                let number_b = numbers[jorunn];
-                if number_a + number_b == val {
-                    is_valid = true;
-                }
-            }
-        }
-
-        if !is_valid {
-            return val;
+fn part_one_solver(numbers: &[isize], preamble_size: usize) -> isize {
+    let mut preamble = PairSums::new(preamble_size);
+    for &number in numbers {
+        if preamble.is_full() && !preamble.contains(number) {
+            return number;
         }
+        preamble.push(number);
     }
 
     0
 }
 
-fn part_two_solver(numbers: &[usize], preamble_size: usize) -> usize {
+fn part_two_solver(numbers: &[isize], preamble_size: usize) -> isize {
     let invalid_number = part_one_solver(numbers, preamble_size);
 
-    for idx_slow in 0..numbers.len() {
-        let mut summers: Vec<usize> = Vec::new();
-        summers.push(numbers[idx_slow]);
-        for next in numbers.iter().skip(idx_slow + 1) {
-            summers.push(*next);
-
-            match summers.iter().sum::<usize>().cmp(&invalid_number) {
-                Ordering::Equal => {
-                    return summers.iter().min().unwrap() + summers.iter().max().unwrap();
-                }
-                Ordering::Greater => {
-                    break;
-                }
-                Ordering::Less => {}
-            }
-        }
-    }
-
-    0
+    // The invalid number is a range of one adding up to itself, so that has to be skipped
+    contiguous_sums_non_negative(numbers, invalid_number)
+        .into_iter()
+        .find(|range| range.len() >= 2)
+        .map_or(0, |range| {
+            let weakness = &numbers[range];
+            weakness.iter().min().unwrap() + weakness.iter().max().unwrap()
+        })
 }
 
-pub fn solve_part_one(input: &[String]) -> usize {
-    part_one_solver(&parse_input(input), 25)
+pub fn solve_part_one(input: &[String], preamble_size: usize) -> isize {
+    part_one_solver(&parse_input(input), preamble_size)
 }
 
-pub fn solve_part_two(input: &[String]) -> usize {
-    part_two_solver(&parse_input(input), 25)
+pub fn solve_part_two(input: &[String], preamble_size: usize) -> isize {
+    part_two_solver(&parse_input(input), preamble_size)
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        127,
-        part_one_solver(
-            &[
-                35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277,
-                309, 576,
-            ],
-            5
-        )
-    );
-}
+/// A preamble of 25 numbers followed by `size` more (between 30 and 1000, as the numbers grow
+/// exponentially), each a sum of two of the 25 before it, apart from one planted invalid number
+/// which is the sum of a contiguous run of earlier numbers instead
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    const PREAMBLE: usize = 25;
+    let len = PREAMBLE + size.clamp(30, 1000);
+
+    // Summing two of the oldest numbers in the window keeps the growth as slow as it can be
+    let valid_next = |rng: &mut Rng, numbers: &[usize]| {
+        let window = &numbers[numbers.len() - PREAMBLE..];
+        let a = rng.below(4);
+        let b = (a + 1 + rng.below(3)) % 4;
+        window[a] + window[b]
+    };
+    let is_pair_sum = |window: &[usize], target: usize| {
+        window
+            .iter()
+            .any(|a| window.iter().any(|b| a + b == target))
+    };
+
+    let mut numbers: Vec<usize> = (1..=50).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(PREAMBLE);
+
+    let invalid_at = rng.range(PREAMBLE as isize + 27, len as isize - 1) as usize;
+    while numbers.len() < len {
+        if numbers.len() == invalid_at {
+            let invalid = loop {
+                let start = rng.below(invalid_at - PREAMBLE - 2);
+                let end = start + rng.range(2, 17).min((invalid_at - start) as isize) as usize;
+                let sum: usize = numbers[start..end].iter().sum();
+                if !is_pair_sum(&numbers[invalid_at - PREAMBLE..], sum) {
+                    break sum;
+                }
+            };
+            numbers.push(invalid);
+        } else {
+            let next = valid_next(rng, &numbers);
+            numbers.push(next);
+        }
+    }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        62,
-        part_two_solver(
-            &[
-                35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277,
-                309, 576,
-            ],
-            5
-        )
-    );
+    numbers.iter().map(|n| n.to_string()).collect()
 }
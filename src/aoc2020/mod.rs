@@ -1,4 +1,4 @@
-use util;
+use runner::{Input, Solution};
 
 mod day1;
 mod day10;
@@ -17,114 +17,140 @@ mod day7;
 mod day8;
 mod day9;
 
-pub fn aoc2020() {
-    println!("Advent of Code 2020");
-
-    println!(
-        "\tDay 1: Part One={}, Part Two={}",
-        day1::solve_part_one(&util::read_file_input("resources/2020/day1.txt")),
-        day1::solve_part_two(&util::read_file_input("resources/2020/day1.txt")),
-    );
-
-    println!(
-        "\tDay 2: Part One={}, Part Two={}",
-        day2::solve_part_one(&util::read_file_input("resources/2020/day2.txt")),
-        day2::solve_part_two(&util::read_file_input("resources/2020/day2.txt")),
-    );
-
-    println!(
-        "\tDay 3: Part One={}, Part Two={}",
-        day3::solve_part_one(&util::read_file_input("resources/2020/day3.txt")),
-        day3::solve_part_two(&util::read_file_input("resources/2020/day3.txt")),
-    );
-
-    println!(
-        "\tDay 4: Part One={}, Part Two={}",
-        day4::solve_part_one(&util::read_file_input_incl_blanks(
-            "resources/2020/day4.txt"
-        )),
-        day4::solve_part_two(&util::read_file_input_incl_blanks(
-            "resources/2020/day4.txt"
-        )),
-    );
-
-    println!(
-        "\tDay 5: Part One={}, Part Two={}",
-        day5::solve_part_one(&util::read_file_input("resources/2020/day5.txt")),
-        day5::solve_part_two(&util::read_file_input("resources/2020/day5.txt")),
-    );
-
-    println!(
-        "\tDay 6: Part One={}, Part Two={}",
-        day6::solve_part_one(&util::read_file_input_incl_blanks(
-            "resources/2020/day6.txt"
-        )),
-        day6::solve_part_two(&util::read_file_input_incl_blanks(
-            "resources/2020/day6.txt"
-        )),
-    );
-
-    println!(
-        "\tDay 7: Part One={}, Part Two={}",
-        day7::solve_part_one(&util::read_file_input("resources/2020/day7.txt")),
-        day7::solve_part_two(&util::read_file_input("resources/2020/day7.txt")),
-    );
-
-    println!(
-        "\tDay 8: Part One={}, Part Two={}",
-        day8::solve_part_one(&util::read_file_input("resources/2020/day8.txt")),
-        day8::solve_part_two(&util::read_file_input("resources/2020/day8.txt")),
-    );
-
-    println!(
-        "\tDay 9: Part One={}, Part Two={}",
-        day9::solve_part_one(&util::read_file_input("resources/2020/day9.txt")),
-        day9::solve_part_two(&util::read_file_input("resources/2020/day9.txt")),
-    );
-
-    println!(
-        "\tDay 10: Part One={}, Part Two={}",
-        day10::solve_part_one(&util::read_file_input("resources/2020/day10.txt")),
-        day10::solve_part_two(&util::read_file_input("resources/2020/day10.txt")),
-    );
-
-    println!(
-        "\tDay 11: Part One={}, Part Two={}",
-        day11::solve_part_one(&util::read_file_input("resources/2020/day11.txt")),
-        day11::solve_part_two(&util::read_file_input("resources/2020/day11.txt")),
-    );
-
-    println!(
-        "\tDay 12: Part One={}, Part Two={}",
-        day12::solve_part_one(&util::read_file_input("resources/2020/day12.txt")),
-        day12::solve_part_two(&util::read_file_input("resources/2020/day12.txt")),
-    );
-
-    println!(
-        "\tDay 13: Part One={}, Part Two={}",
-        day13::solve_part_one(&util::read_file_input("resources/2020/day13.txt")),
-        day13::solve_part_two(&util::read_file_input("resources/2020/day13.txt")),
-    );
-
-    println!(
-        "\tDay 14: Part One={}, Part Two={}",
-        day14::solve_part_one(&util::read_file_input("resources/2020/day14.txt")),
-        day14::solve_part_two(&util::read_file_input("resources/2020/day14.txt")),
-    );
-
-    println!(
-        "\tDay 15: Part One={}, Part Two={}",
-        day15::solve_part_one(&util::read_file_input("resources/2020/day15.txt")),
-        day15::solve_part_two(&util::read_file_input("resources/2020/day15.txt")),
-    );
-
-    println!(
-        "\tDay 16: Part One={}, Part Two={}",
-        day16::solve_part_one(&util::read_file_input_incl_blanks(
-            "resources/2020/day16.txt"
-        )),
-        day16::solve_part_two(&util::read_file_input_incl_blanks(
-            "resources/2020/day16.txt"
-        )),
-    );
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution {
+            year: 2020,
+            day: 1,
+            input: Input::Lines("resources/2020/day1.txt"),
+            part_one: |i, _| day1::solve_part_one(i).to_string(),
+            part_two: |i, _| day1::solve_part_two(i).to_string(),
+            generate: day1::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 2,
+            input: Input::Lines("resources/2020/day2.txt"),
+            part_one: |i, _| day2::solve_part_one(i).to_string(),
+            part_two: |i, _| day2::solve_part_two(i).to_string(),
+            generate: day2::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 3,
+            input: Input::Lines("resources/2020/day3.txt"),
+            part_one: |i, _| day3::solve_part_one(i).to_string(),
+            part_two: |i, _| day3::solve_part_two(i).to_string(),
+            generate: day3::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 4,
+            input: Input::LinesInclBlanks("resources/2020/day4.txt"),
+            part_one: |i, _| day4::solve_part_one(i).to_string(),
+            part_two: |i, _| day4::solve_part_two(i).to_string(),
+            generate: day4::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 5,
+            input: Input::Lines("resources/2020/day5.txt"),
+            part_one: |i, _| day5::solve_part_one(i).to_string(),
+            part_two: |i, _| day5::solve_part_two(i).to_string(),
+            generate: day5::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 6,
+            input: Input::LinesInclBlanks("resources/2020/day6.txt"),
+            part_one: |i, _| day6::solve_part_one(i).to_string(),
+            part_two: |i, _| day6::solve_part_two(i).to_string(),
+            generate: day6::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 7,
+            input: Input::Lines("resources/2020/day7.txt"),
+            part_one: |i, _| day7::solve_part_one(i).to_string(),
+            part_two: |i, _| day7::solve_part_two(i).to_string(),
+            generate: day7::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 8,
+            input: Input::Lines("resources/2020/day8.txt"),
+            part_one: |i, _| day8::solve_part_one(i).to_string(),
+            part_two: |i, _| day8::solve_part_two(i).to_string(),
+            generate: day8::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 9,
+            input: Input::Lines("resources/2020/day9.txt"),
+            // The examples look back over a preamble of 5, the real input over 25
+            part_one: |i, p| day9::solve_part_one(i, p.get("preamble", 25)).to_string(),
+            part_two: |i, p| day9::solve_part_two(i, p.get("preamble", 25)).to_string(),
+            generate: day9::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 10,
+            input: Input::Lines("resources/2020/day10.txt"),
+            // `--param histogram=1` shows every joltage difference rather than just the answer
+            part_one: |i, p| match p.get("histogram", 0) {
+                0 => day10::solve_part_one(i).to_string(),
+                _ => day10::difference_report(i),
+            },
+            part_two: |i, _| day10::solve_part_two(i).to_string(),
+            generate: day10::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 11,
+            input: Input::Lines("resources/2020/day11.txt"),
+            part_one: |i, _| day11::solve_part_one(i).to_string(),
+            part_two: |i, _| day11::solve_part_two(i).to_string(),
+            generate: day11::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 12,
+            input: Input::Lines("resources/2020/day12.txt"),
+            part_one: |i, _| day12::solve_part_one(i).to_string(),
+            part_two: |i, _| day12::solve_part_two(i).to_string(),
+            generate: day12::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 13,
+            input: Input::Lines("resources/2020/day13.txt"),
+            part_one: |i, _| day13::solve_part_one(i).to_string(),
+            part_two: |i, _| day13::solve_part_two(i).to_string(),
+            generate: day13::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 14,
+            input: Input::Lines("resources/2020/day14.txt"),
+            part_one: |i, _| day14::solve_part_one(i).to_string(),
+            part_two: |i, _| day14::solve_part_two(i).to_string(),
+            generate: day14::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 15,
+            input: Input::Lines("resources/2020/day15.txt"),
+            part_one: |i, _| day15::solve_part_one(i).to_string(),
+            part_two: |i, _| day15::solve_part_two(i).to_string(),
+            generate: day15::generate,
+        },
+        Solution {
+            year: 2020,
+            day: 16,
+            input: Input::LinesInclBlanks("resources/2020/day16.txt"),
+            part_one: |i, _| day16::solve_part_one(i).to_string(),
+            part_two: |i, _| day16::solve_part_two(i).to_string(),
+            generate: day16::generate,
+        },
+    ]
 }
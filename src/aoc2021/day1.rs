@@ -1,3 +1,5 @@
+use rng::Rng;
+
 /// --- Day 1: Sonar Sweep ---
 ///
 /// You're minding your own business on a ship at sea when the overboard alarm goes off!
@@ -124,40 +126,13 @@ pub fn solve_part_two(input: &[String]) -> usize {
         .count()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        7,
-        solve_part_one(&[
-            "199".to_string(),
-            "200".to_string(),
-            "208".to_string(),
-            "210".to_string(),
-            "200".to_string(),
-            "207".to_string(),
-            "240".to_string(),
-            "269".to_string(),
-            "260".to_string(),
-            "263".to_string(),
-        ])
-    );
-}
-
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        5,
-        solve_part_two(&[
-            "199".to_string(),
-            "200".to_string(),
-            "208".to_string(),
-            "210".to_string(),
-            "200".to_string(),
-            "207".to_string(),
-            "240".to_string(),
-            "269".to_string(),
-            "260".to_string(),
-            "263".to_string(),
-        ])
-    );
+/// `size` sonar depth readings, wandering generally deeper as the real readings do
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut depth = rng.range(100, 200);
+    (0..size.max(1))
+        .map(|_| {
+            depth = (depth + rng.range(-10, 20)).max(0);
+            depth.to_string()
+        })
+        .collect()
 }
@@ -1,8 +1,8 @@
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::digit1;
-use nom::combinator::map_res;
 use nom::IResult;
+use parse::{parse_lines, unsigned};
+use rng::Rng;
 
 /// --- Day 2: Dive! ---
 ///
@@ -83,7 +83,7 @@ enum Command {
 fn parse_command(input: &str) -> IResult<&str, Command> {
     let (input, cmd) = alt((tag("forward"), tag("down"), tag("up")))(input)?;
     let (input, _) = tag(" ")(input)?;
-    let (input, arg) = map_res(digit1, |s: &str| s.parse::<usize>())(input)?;
+    let (input, arg) = unsigned(input)?;
 
     Ok((
         input,
@@ -97,30 +97,23 @@ fn parse_command(input: &str) -> IResult<&str, Command> {
 }
 
 pub fn solve_part_one(input: &[String]) -> usize {
-    let (horizontal_position, depth) = input
-        .iter()
-        .map(|command| {
-            parse_command(command.as_str())
-                .map(|(_, instr)| instr)
-                .unwrap()
-        })
-        .fold((0, 0), |acc, cmd| match cmd {
-            Command::Forward(dist) => (acc.0 + dist, acc.1),
-            Command::Down(dist) => (acc.0, acc.1 + dist),
-            Command::Up(dist) => (acc.0, acc.1 - dist),
-        });
+    let (horizontal_position, depth) =
+        parse_lines(input, parse_command)
+            .unwrap()
+            .into_iter()
+            .fold((0, 0), |acc, cmd| match cmd {
+                Command::Forward(dist) => (acc.0 + dist, acc.1),
+                Command::Down(dist) => (acc.0, acc.1 + dist),
+                Command::Up(dist) => (acc.0, acc.1 - dist),
+            });
 
     horizontal_position * depth
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
-    let (horizontal_position, depth, _aim) = input
-        .iter()
-        .map(|command| {
-            parse_command(command.as_str())
-                .map(|(_, instr)| instr)
-                .unwrap()
-        })
+    let (horizontal_position, depth, _aim) = parse_lines(input, parse_command)
+        .unwrap()
+        .into_iter()
         .fold((0, 0, 0), |acc, cmd| match cmd {
             Command::Forward(dist) => (acc.0 + dist, acc.1 + dist * acc.2, acc.2),
             Command::Down(dist) => (acc.0, acc.1, acc.2 + dist),
@@ -130,32 +123,23 @@ pub fn solve_part_two(input: &[String]) -> usize {
     horizontal_position * depth
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        150,
-        solve_part_one(&[
-            "forward 5".to_string(),
-            "down 5".to_string(),
-            "forward 8".to_string(),
-            "up 3".to_string(),
-            "down 8".to_string(),
-            "forward 2".to_string(),
-        ])
-    );
-}
-
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        900,
-        solve_part_two(&[
-            "forward 5".to_string(),
-            "down 5".to_string(),
-            "forward 8".to_string(),
-            "up 3".to_string(),
-            "down 8".to_string(),
-            "forward 2".to_string(),
-        ])
-    );
+/// `size` submarine commands. Depth and aim are both unsigned, so the submarine never goes up past
+/// the surface under either reading of the commands.
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut aim = 0;
+    (0..size.max(1))
+        .map(|_| match rng.below(3) {
+            0 => format!("forward {}", rng.range(1, 9)),
+            1 if aim > 0 => {
+                let up = rng.range(1, aim.min(9));
+                aim -= up;
+                format!("up {}", up)
+            }
+            _ => {
+                let down = rng.range(1, 9);
+                aim += down;
+                format!("down {}", down)
+            }
+        })
+        .collect()
 }
@@ -1,3 +1,6 @@
+use bits::{column_counts, count_column, low_bits, parse_binary};
+use rng::Rng;
+
 /// --- Day 3: Binary Diagnostic ---
 ///
 /// The submarine has been making some odd creaking noises, so you ask it to produce a diagnostic
@@ -109,61 +112,27 @@
 /// What is the life support rating of the submarine?
 /// (Be sure to represent your answer in decimal, not binary.)
 
-fn parse_inputs(input: &[String]) -> Vec<usize> {
+fn parse_inputs(input: &[String]) -> Vec<u64> {
     input
         .iter()
-        .map(|diagnostic| usize::from_str_radix(diagnostic, 2).unwrap())
+        .map(|diagnostic| parse_binary(diagnostic))
         .collect()
 }
 
 pub fn solve_part_one(input: &[String]) -> usize {
-    let input_bits = input[0].len();
+    let input_bits = input[0].len() as u32;
 
-    let gamma = parse_inputs(input)
-        .iter()
-        .fold(vec![0; input_bits], |bit_counts, diagnostic| {
-            bit_counts
-                .iter()
-                .enumerate()
-                .map(|(bit_index, &bit_count)| bit_count + (diagnostic >> bit_index & 1))
-                .collect()
-        })
+    // Each bit of gamma is whichever is most common in that column of the report
+    let gamma = column_counts(&parse_inputs(input), input_bits)
         .iter()
         .enumerate()
-        // .inspect(|(bit_index, &bit_count)| println!("[{}] {}", bit_index, bit_count))
-        .map(|(bit_index, &bit_count)| {
-            if bit_count > input.len() / 2 {
-                1 << bit_index
-            } else {
-                0
-            }
-        })
-        .sum::<usize>();
-
-    let epsilon = !gamma & ((1 << input_bits) - 1);
+        .filter(|(_, &bit_count)| bit_count > input.len() / 2)
+        .map(|(bit_index, _)| 1 << bit_index)
+        .sum::<u64>();
 
-    gamma * epsilon
-}
+    let epsilon = !gamma & low_bits(input_bits);
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        198,
-        solve_part_one(&[
-            "00100".to_string(),
-            "11110".to_string(),
-            "10110".to_string(),
-            "10111".to_string(),
-            "10101".to_string(),
-            "01111".to_string(),
-            "00111".to_string(),
-            "11100".to_string(),
-            "10000".to_string(),
-            "11001".to_string(),
-            "00010".to_string(),
-            "01010".to_string(),
-        ])
-    );
+    (gamma * epsilon) as usize
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
@@ -181,45 +150,30 @@ fn find_rating_in_report<F>(input: &[String], bit_count_test: F) -> usize
 where
     F: Fn(usize, usize) -> bool,
 {
-    let input_bits = input[0].len();
+    let input_bits = input[0].len() as u32;
     (0..input_bits)
         .rev()
         .scan(parse_inputs(input), |matching_inputs, bit_index| {
             if matching_inputs.len() > 1 {
                 let filter_for_ones = bit_count_test(
-                    matching_inputs
-                        .iter()
-                        .filter(|&matching_input| matching_input & 1 << bit_index > 0)
-                        .count(),
+                    count_column(matching_inputs, bit_index),
                     (matching_inputs.len() + 1) / 2,
                 );
                 matching_inputs.retain(|&matching_input| {
-                    (matching_input & 1 << bit_index > 0) == filter_for_ones
+                    (matching_input >> bit_index & 1 == 1) == filter_for_ones
                 });
             }
             matching_inputs.last().copied()
         })
         .last()
-        .unwrap()
+        .unwrap() as usize
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        230,
-        solve_part_two(&[
-            "00100".to_string(),
-            "11110".to_string(),
-            "10110".to_string(),
-            "10111".to_string(),
-            "10101".to_string(),
-            "01111".to_string(),
-            "00111".to_string(),
-            "11100".to_string(),
-            "10000".to_string(),
-            "11001".to_string(),
-            "00010".to_string(),
-            "01010".to_string(),
-        ])
-    );
+/// `size` distinct 12 bit numbers from the diagnostic report, at most the 4096 there are
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut numbers: Vec<usize> = (0..4096).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(size.clamp(1, 4096));
+
+    numbers.iter().map(|n| format!("{:012b}", n)).collect()
 }
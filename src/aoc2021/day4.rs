@@ -1,4 +1,5 @@
 use aoc2021::day4::MarkableCell::{Marked, Unmarked};
+use rng::Rng;
 use std::fmt;
 
 /// --- Day 4: Giant Squid ---
@@ -229,25 +230,30 @@ pub fn solve_part_two(input: &[String]) -> usize {
     panic!("Did not find the last winning board")
 }
 
-#[cfg(test)]
-mod tests {
-    use aoc2021::day4::solve_part_one;
-    use aoc2021::day4::solve_part_two;
-    use util::read_file_input;
-
-    #[test]
-    fn examples_part_one() {
-        assert_eq!(
-            4512,
-            solve_part_one(&read_file_input("resources/2021/day4_example.txt"))
-        );
-    }
+/// Every number from 0 to 99 drawn in a random order, and `size` boards of 25 of them
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut numbers: Vec<usize> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let mut lines = vec![numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(",")];
 
-    #[test]
-    fn examples_part_two() {
-        assert_eq!(
-            1924,
-            solve_part_two(&read_file_input("resources/2021/day4_example.txt"))
+    for _ in 0..size.max(1) {
+        rng.shuffle(&mut numbers);
+        lines.push(String::new());
+        lines.extend(
+            numbers[..BOARD_SIZE * BOARD_SIZE]
+                .chunks(BOARD_SIZE)
+                .map(|row| {
+                    row.iter()
+                        .map(|n| format!("{:>2}", n))
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                }),
         );
     }
+
+    lines
 }
@@ -1,12 +1,15 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt;
 
-use nom::bytes::complete::tag;
-use nom::character::complete::digit1;
-use nom::combinator::map_res;
-use nom::sequence::separated_pair;
 use nom::IResult;
 
+use geometry::Segment;
+use parse::{arrow, coordinate, parse_line};
+use point::Point2;
+use rng::Rng;
+#[cfg(test)]
+use sparse_grid::SparseGrid;
+
 /// --- Day 5: Hydrothermal Venture ---
 ///
 /// You come across a field of hydrothermal vents on the ocean floor! These vents constantly produce
@@ -117,18 +120,14 @@ impl fmt::Display for Line {
 
 impl Line {
     pub fn new(line_descriptor: &str) -> Line {
-        match Line::parse_line(line_descriptor) {
-            Ok((_, line)) => line,
-            Err(e) => panic!("Error parsing line {:?}", e),
+        match parse_line(line_descriptor, Line::parse_line) {
+            Ok(line) => line,
+            Err(e) => panic!("Error parsing line {}", e),
         }
     }
 
     fn parse_line(input: &str) -> IResult<&str, Line> {
-        let (input, (start_x, start_y)) =
-            separated_pair(Line::parse_digits, tag(","), Line::parse_digits)(input)?;
-        let (input, _) = tag(" -> ")(input)?;
-        let (input, (end_x, end_y)) =
-            separated_pair(Line::parse_digits, tag(","), Line::parse_digits)(input)?;
+        let (input, ((start_x, start_y), (end_x, end_y))) = arrow(coordinate, coordinate)(input)?;
 
         Ok((
             input,
@@ -140,79 +139,117 @@ impl Line {
             },
         ))
     }
-    fn parse_digits(input: &str) -> IResult<&str, isize> {
-        map_res(digit1, |s: &str| s.parse::<isize>())(input)
-    }
 
     pub fn is_horizontal_or_vertical(&self) -> bool {
         self.start_x == self.end_x || self.start_y == self.end_y
     }
 
+    fn segment(&self) -> Segment {
+        Segment::new(
+            Point2::new(self.start_x, self.start_y),
+            Point2::new(self.end_x, self.end_y),
+        )
+    }
+
+    /// The points the line covers, which are the grid points it passes exactly through so they
+    /// always agree with the intersections used to find overlaps
+    #[cfg(test)]
     pub fn get_line_points(&self) -> Vec<(isize, isize)> {
-        let dx = self.end_x - self.start_x;
-        let dy = self.end_y - self.start_y;
-
-        (0..=(dx.abs().max(dy.abs())))
-            .map(|offset| {
-                let x = self.start_x + offset * dx.signum();
-                let y = self.start_y + offset * dy.signum();
-                (x, y)
-            })
+        self.segment()
+            .lattice_points()
+            .iter()
+            .map(|p| (p.x, p.y))
             .collect()
     }
 }
 
+/// Every point where at least two lines overlap, found by intersecting each pair of lines rather
+/// than walking every point of every line
+///
+/// A line covers exactly the grid points it passes through, the same as `Line::get_line_points`,
+/// so for the horizontal, vertical and 45° vents in the puzzle that's every cell along the way.
+fn overlap_points(lines: &[Line]) -> HashSet<Point2> {
+    let segments: Vec<Segment> = lines.iter().map(Line::segment).collect();
+
+    let mut overlaps = HashSet::new();
+    for (i, a) in segments.iter().enumerate() {
+        for b in &segments[i + 1..] {
+            overlaps.extend(a.lattice_intersections(b));
+        }
+    }
+    overlaps
+}
+
+/// Draw how many lines cover each point the way the puzzle does, handy for debugging
+#[cfg(test)]
+fn draw_lines(lines: &[Line]) -> String {
+    let coord_counts = SparseGrid::count_points(
+        lines
+            .iter()
+            .flat_map(|line| line.get_line_points())
+            .map(|(x, y)| Point2::new(x, y)),
+    );
+
+    coord_counts.render(|count| match count {
+        None => '.',
+        Some(count) => std::char::from_digit(*count as u32, 36).unwrap_or('+'),
+    })
+}
+
 pub fn solve_part_one(input: &[String]) -> usize {
-    input
+    let lines: Vec<Line> = input
         .iter()
         .map(|line_desc| Line::new(line_desc))
         .filter(|line| line.is_horizontal_or_vertical())
-        .flat_map(|line| line.get_line_points())
-        .scan(HashMap::new(), |state, point| {
-            *state.entry(point).or_insert(0) += 1;
-            Some(state.clone())
-        })
-        .last()
-        .unwrap()
-        .values()
-        .filter(|&v| v > &1)
-        .count()
+        .collect();
+
+    overlap_points(&lines).len()
 }
 
 pub fn solve_part_two(input: &[String]) -> usize {
-    let coord_counts = input
-        .iter()
-        .map(|line_desc| Line::new(line_desc))
-        .flat_map(|line| line.get_line_points())
-        .scan(HashMap::new(), |state, point| {
-            *state.entry(point).or_insert(0) += 1;
-            Some(state.clone())
+    let lines: Vec<Line> = input.iter().map(|line_desc| Line::new(line_desc)).collect();
+
+    overlap_points(&lines).len()
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let side = (size as f64).sqrt() as isize * 45 + 10;
+    // The puzzle's own inputs only use the first eight, the steeper and shallower slopes are there
+    // to check lines that skip past cells they only clip
+    let directions = [
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+        (2, 1),
+        (-1, 2),
+        (1, -3),
+        (-3, -2),
+    ];
+
+    (0..size.max(1))
+        .map(|_| {
+            let (x, y) = (rng.range(0, side - 1), rng.range(0, side - 1));
+            let (dx, dy) = *rng.choose(&directions);
+            let room = |pos: isize, d: isize| match d.signum() {
+                1 => (side - 1 - pos) / d,
+                -1 => pos / -d,
+                _ => side,
+            };
+            let length = rng.range(0, room(x, dx).min(room(y, dy)));
+
+            format!("{},{} -> {},{}", x, y, x + dx * length, y + dy * length)
         })
-        .last()
-        .unwrap();
-
-    // Debug draw counts
-    // let max_x = *coord_counts.keys().map(|(x, _y)| x).max().unwrap();
-    // let max_y = *coord_counts.keys().map(|(_x, y)| y).max().unwrap();
-    //
-    // (0..=max_y).for_each(|y| {
-    //     (0..=max_x).for_each(|x| match coord_counts.get(&(x as isize, y as isize)) {
-    //         None => {
-    //             print!(".")
-    //         }
-    //         Some(count) => {
-    //             print!("{}", count)
-    //         }
-    //     });
-    //     println!();
-    // });
-
-    coord_counts.values().filter(|&v| v > &1).count()
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc2021::day5::{solve_part_one, solve_part_two, Line};
+    use aoc2021::day5::{draw_lines, solve_part_two, Line};
     use util::read_file_input;
 
     #[test]
@@ -261,21 +298,174 @@ mod tests {
             vec![(9, 7), (8, 8), (7, 9)],
             Line::new("9,7 -> 7,9").get_line_points()
         );
+
+        assert_eq!(
+            vec![(0, 0), (2, 1), (4, 2)],
+            Line::new("0,0 -> 4,2").get_line_points()
+        );
     }
 
     #[test]
-    fn examples_part_one() {
+    fn other_slopes_agree_with_drawing() {
+        let input = vec!["0,0 -> 4,2".to_string(), "2,0 -> 2,3".to_string()];
+        let lines: Vec<Line> = input.iter().map(|l| Line::new(l)).collect();
+
         assert_eq!(
-            5,
-            solve_part_one(&read_file_input("resources/2021/day5_example.txt"))
+            ["1.1..", "..2..", "..1.1", "..1.."].join("\n"),
+            draw_lines(&lines)
         );
+        assert_eq!(1, solve_part_two(&input));
+
+        let clipped = vec!["0,0 -> 4,2".to_string(), "1,0 -> 1,3".to_string()];
+        assert_eq!(0, solve_part_two(&clipped));
     }
 
     #[test]
-    fn examples_part_two() {
+    fn drawing_counts() {
+        let lines: Vec<Line> = read_file_input("resources/examples/2021/day5/example.txt")
+            .iter()
+            .map(|l| Line::new(l))
+            .collect();
+
         assert_eq!(
-            12,
-            solve_part_two(&read_file_input("resources/2021/day5_example.txt"))
+            [
+                "1.1....11.",
+                ".111...2..",
+                "..2.1.111.",
+                "...1.2.2..",
+                ".112313211",
+                "...1.2....",
+                "..1...1...",
+                ".1.....1..",
+                "1.......1.",
+                "222111....",
+            ]
+            .join("\n"),
+            draw_lines(&lines)
+        );
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use super::*;
+    use property;
+
+    /// A generated input of random size
+    fn any_input(rng: &mut Rng) -> Vec<String> {
+        let size = rng.below(30) + 1;
+        generate(rng, size)
+    }
+
+    /// Count overlaps on a dense grid, independently of the line intersections used by the solutions
+    fn dense_overlap_count(lines: &[Line]) -> usize {
+        let width = lines
+            .iter()
+            .map(|l| l.start_x.max(l.end_x))
+            .max()
+            .unwrap_or(0) as usize
+            + 1;
+        let height = lines
+            .iter()
+            .map(|l| l.start_y.max(l.end_y))
+            .max()
+            .unwrap_or(0) as usize
+            + 1;
+        let mut grid = vec![0usize; width * height];
+        for line in lines {
+            for (x, y) in line.get_line_points() {
+                grid[y as usize * width + x as usize] += 1;
+            }
+        }
+
+        grid.iter().filter(|&&c| c > 1).count()
+    }
+
+    #[test]
+    fn property_line_points_walk_from_start_to_end() {
+        property::check(
+            "line points step evenly from start to end",
+            any_input,
+            |input| {
+                for line in input.iter().map(|l| Line::new(l)) {
+                    let points = line.get_line_points();
+                    let steps: Vec<(isize, isize)> = points
+                        .windows(2)
+                        .map(|w| (w[1].0 - w[0].0, w[1].1 - w[0].1))
+                        .collect();
+                    let steps_are_even = steps.windows(2).all(|w| w[0] == w[1]);
+                    let covers_every_cell = line.is_horizontal_or_vertical()
+                        || (line.end_x - line.start_x).abs() == (line.end_y - line.start_y).abs();
+                    let steps_are_adjacent =
+                        steps.iter().all(|(dx, dy)| dx.abs() <= 1 && dy.abs() <= 1);
+
+                    if points.first() != Some(&(line.start_x, line.start_y))
+                        || points.last() != Some(&(line.end_x, line.end_y))
+                        || !steps_are_even
+                        || (covers_every_cell && !steps_are_adjacent)
+                    {
+                        return Err(format!("{} gave points {:?}", line, points));
+                    }
+                }
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn property_part_two_matches_dense_grid() {
+        property::check("part two matches a dense grid count", any_input, |input| {
+            let lines: Vec<Line> = input.iter().map(|l| Line::new(l)).collect();
+            let expected = dense_overlap_count(&lines);
+            let actual = solve_part_two(input);
+            if expected == actual {
+                Ok(())
+            } else {
+                Err(format!("expected {}, got {}", expected, actual))
+            }
+        });
+    }
+
+    #[test]
+    fn property_diagonals_only_add_overlaps() {
+        property::check(
+            "part one never finds more overlaps than part two",
+            any_input,
+            |input| {
+                let (one, two) = (solve_part_one(input), solve_part_two(input));
+                if one <= two {
+                    Ok(())
+                } else {
+                    Err(format!("part one {} > part two {}", one, two))
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn property_line_direction_does_not_matter() {
+        property::check(
+            "reversing every line keeps the same answers",
+            any_input,
+            |input| {
+                let reversed: Vec<String> = input
+                    .iter()
+                    .map(|l| {
+                        let (start, end) = l.split_once(" -> ").unwrap();
+                        format!("{} -> {}", end, start)
+                    })
+                    .collect();
+                let forwards = (solve_part_one(input), solve_part_two(input));
+                let backwards = (solve_part_one(&reversed), solve_part_two(&reversed));
+                if forwards == backwards {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{:?} forwards, {:?} backwards",
+                        forwards, backwards
+                    ))
+                }
+            },
         );
     }
 }
@@ -1,3 +1,6 @@
+use matrix::Matrix;
+use rng::Rng;
+
 /// --- Day 6: Lanternfish ---
 ///
 /// The sea floor is getting steeper. Maybe the sleigh keys got carried this way?
@@ -77,46 +80,127 @@
 ///
 /// How many lanternfish would there be after 256 days?
 
-fn simulate_fish_generations(fish_ages: Vec<usize>, generations: usize) -> usize {
-    let mut age_buckets = [0usize; 9];
-    fish_ages.iter().for_each(|&age| age_buckets[age] += 1);
+/// How lanternfish breed: a fish's timer counts down each day, and a day after reaching 0 it
+/// spawns a new fish with its timer at `spawn` while its own timer goes back to `reset`
+#[derive(Debug, Clone, Copy)]
+struct Lanternfish {
+    reset: usize,
+    spawn: usize,
+}
 
-    for _generation in 0..generations {
-        let fish_spawning = age_buckets[0];
-        for age in 0..=7 {
-            age_buckets[age] = age_buckets[age + 1];
-        }
-        age_buckets[8] = fish_spawning;
-        age_buckets[6] += fish_spawning;
+const LANTERNFISH: Lanternfish = Lanternfish { reset: 6, spawn: 8 };
+
+impl Lanternfish {
+    /// How one day changes the number of fish with each timer
+    fn day(&self) -> Matrix {
+        assert!(
+            self.reset <= self.spawn,
+            "Fish reset to {} but spawn at {}, timers only go up to the spawn timer",
+            self.reset,
+            self.spawn
+        );
+        let mut day = Matrix::from_fn(self.spawn + 1, |timer, previous| {
+            (previous == timer + 1) as u128
+        });
+        day[(self.reset, 0)] += 1;
+        day[(self.spawn, 0)] += 1;
+        day
     }
 
-    age_buckets.iter().sum()
+    /// How many fish there are after `generations` days, starting with a fish for each timer,
+    /// either exactly or modulo `modulus`
+    ///
+    /// Exact counts are `None` if they outgrow a `u128`, which lanternfish do after about 1,000
+    /// days.
+    fn population(
+        &self,
+        timers: &[usize],
+        generations: u64,
+        modulus: Option<u128>,
+    ) -> Option<u128> {
+        let mut fish = vec![0; self.spawn + 1];
+        for &timer in timers {
+            assert!(
+                timer <= self.spawn,
+                "Timer {} is past the spawn timer {}",
+                timer,
+                self.spawn
+            );
+            fish[timer] += 1;
+        }
+
+        self.day()
+            .pow(generations, modulus)?
+            .apply(&fish, modulus)?
+            .into_iter()
+            .try_fold(0, |total: u128, count| match modulus {
+                Some(m) => Some((total + count) % m),
+                None => total.checked_add(count),
+            })
+    }
 }
 
-pub fn solve_part_one(input: &[String]) -> usize {
+fn parse_timers(input: &[String]) -> Vec<usize> {
     assert_eq!(1, input.len());
-    let fish_ages = input[0]
-        .split(',')
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<usize>>();
-    simulate_fish_generations(fish_ages, 80)
+    input[0].split(',').map(|i| i.parse().unwrap()).collect()
+}
+
+pub fn solve_part_one(input: &[String]) -> u128 {
+    LANTERNFISH
+        .population(&parse_timers(input), 80, None)
+        .unwrap()
+}
+
+pub fn solve_part_two(input: &[String]) -> u128 {
+    LANTERNFISH
+        .population(&parse_timers(input), 256, None)
+        .unwrap()
 }
 
 #[test]
-fn examples_part_one() {
-    assert_eq!(5934, solve_part_one(&["3,4,3,1,2".to_string(),]));
+fn test_population() {
+    let timers = [3, 4, 3, 1, 2];
+    assert_eq!(Some(5), LANTERNFISH.population(&timers, 0, None));
+    assert_eq!(Some(26), LANTERNFISH.population(&timers, 18, None));
+
+    // Far too many to count exactly, but fine modulo a prime, where days can be split up
+    assert_eq!(None, LANTERNFISH.population(&timers, 10_000, None));
+    let modulus = Some(1_000_000_007);
+    let population = LANTERNFISH.population(&timers, 1_000_000_000_000_000_000, modulus);
+    assert!(population.is_some_and(|p| p < 1_000_000_007));
+    for p in [1_000_000_007, 998_244_353, 65_537] {
+        assert_eq!(
+            Some(26984457539 % p),
+            LANTERNFISH.population(&timers, 256, Some(p))
+        );
+    }
+    let day = LANTERNFISH.day();
+    let split = day
+        .pow(999_999_999_999_999_999, modulus)
+        .and_then(|days| days.mul(&day, modulus));
+    assert_eq!(day.pow(1_000_000_000_000_000_000, modulus), split);
+
+    // A quicker breeding fish doubles every day
+    let rabbits = Lanternfish { reset: 0, spawn: 0 };
+    assert_eq!(Some(1 << 100), rabbits.population(&[0], 100, None));
 }
 
-pub fn solve_part_two(input: &[String]) -> usize {
-    assert_eq!(1, input.len());
-    let fish_ages = input[0]
-        .split(',')
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<usize>>();
-    simulate_fish_generations(fish_ages, 256)
+#[test]
+#[should_panic(expected = "past the spawn timer")]
+fn test_population_timer_past_spawn() {
+    LANTERNFISH.population(&[9], 1, None);
 }
 
 #[test]
-fn examples_part_two() {
-    assert_eq!(26984457539, solve_part_two(&["3,4,3,1,2".to_string(),]));
+#[should_panic(expected = "timers only go up to the spawn timer")]
+fn test_reset_past_spawn() {
+    Lanternfish { reset: 9, spawn: 8 }.day();
+}
+
+/// `size` lanternfish, with the same spread of starting timers as the real puzzle input
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    vec![(0..size.max(1))
+        .map(|_| rng.range(1, 5).to_string())
+        .collect::<Vec<String>>()
+        .join(",")]
 }
@@ -1,3 +1,6 @@
+use rng::Rng;
+use search::minimise_convex;
+
 /// --- Day 7: The Treachery of Whales ---
 ///
 /// A giant whale has decided your submarine is its next meal, and it's much faster than you are.
@@ -74,48 +77,147 @@
 /// Determine the horizontal position that the crabs can align to using the least fuel possible so
 /// they can make you an escape route! How much fuel must they spend to align to that position?
 
-pub fn solve_part_one(input: &[String]) -> isize {
+fn parse_positions(input: &[String]) -> Vec<isize> {
     assert_eq!(1, input.len());
-    let crab_positions = input[0]
-        .split(',')
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<isize>>();
+    input[0].split(',').map(|i| i.parse().unwrap()).collect()
+}
 
-    let max_position = crab_positions.iter().max().unwrap();
-    let fuel_cost = |a: isize, b: isize| (a - b).abs();
+/// Each step costs one more fuel than the last
+fn triangular(distance: isize) -> isize {
+    distance * (distance + 1) / 2
+}
 
-    (0..=*max_position)
-        .map(|i| crab_positions.iter().map(|&x| fuel_cost(x, i)).sum())
-        .min()
+/// The fuel for every crab to move to `position`, given how much fuel moving a distance takes
+fn total_fuel<F: Fn(isize) -> isize>(crabs: &[isize], position: isize, fuel: F) -> isize {
+    crabs.iter().map(|crab| fuel((crab - position).abs())).sum()
+}
+
+/// Where the crabs should line up when each step costs one fuel, and the fuel that takes
+///
+/// Moving away from the median moves further from at least as many crabs as it moves closer to.
+fn align_linear(crabs: &[isize]) -> (isize, isize) {
+    let mut sorted = crabs.to_vec();
+    sorted.sort_unstable();
+    let median = sorted[(sorted.len() - 1) / 2];
+    (median, total_fuel(crabs, median, |d| d))
+}
+
+/// Where the crabs should line up when each step costs one more fuel than the last, and the fuel
+/// that takes
+///
+/// The total fuel is half the sum of squared distances plus half the sum of distances, so the
+/// best position is the mean nudged by at most half a step, and only its neighbours need trying.
+fn align_triangular(crabs: &[isize]) -> (isize, isize) {
+    let mean = crabs.iter().sum::<isize>().div_euclid(crabs.len() as isize);
+    (mean - 1..=mean + 2)
+        .map(|position| (position, total_fuel(crabs, position, triangular)))
+        .min_by_key(|&(position, fuel)| (fuel, position))
         .unwrap()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(37, solve_part_one(&["16,1,2,0,4,2,7,1,2,14".to_string(),]));
+/// Where the crabs should line up for any fuel cost that's convex in the distance moved, and the
+/// fuel that takes, by searching between the outermost crabs as the total fuel is convex in the
+/// position too
+///
+/// The search needs the total fuel at a couple of positions per halving, which is slower than the
+/// closed forms above, so it's for costs that don't have one.
+fn align_convex<F: Fn(isize) -> isize>(crabs: &[isize], fuel: F) -> (isize, isize) {
+    let (low, high) = (crabs.iter().min().unwrap(), crabs.iter().max().unwrap());
+    minimise_convex(*low, *high, |position| total_fuel(crabs, position, &fuel))
 }
 
-pub fn solve_part_two(input: &[String]) -> isize {
-    assert_eq!(1, input.len());
-    let crab_positions = input[0]
-        .split(',')
-        .map(|i| i.parse().unwrap())
-        .collect::<Vec<isize>>();
+/// How much fuel a crab burns moving a distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fuel {
+    /// One fuel per step
+    Linear,
+    /// Each step costs one more fuel than the last
+    Triangular,
+    /// The distance to the given power, which is convex for any power of at least one
+    Power(u32),
+}
+
+impl Fuel {
+    fn cost(&self, distance: isize) -> isize {
+        match self {
+            Fuel::Linear => distance,
+            Fuel::Triangular => triangular(distance),
+            Fuel::Power(power) => distance.pow(*power),
+        }
+    }
 
-    let max_position = crab_positions.iter().max().unwrap();
+    /// Where the crabs should line up and the fuel that takes, using a closed form where there is
+    /// one and searching otherwise
+    fn align(&self, crabs: &[isize]) -> (isize, isize) {
+        match self {
+            Fuel::Linear => align_linear(crabs),
+            Fuel::Triangular => align_triangular(crabs),
+            Fuel::Power(power) => {
+                assert!(*power >= 1, "Fuel has to grow with distance to be convex");
+                align_convex(crabs, |distance| self.cost(distance))
+            }
+        }
+    }
+}
+
+/// The least fuel it takes to line the crabs up, for any way of burning fuel
+pub fn solve_with_fuel(input: &[String], fuel: Fuel) -> isize {
+    fuel.align(&parse_positions(input)).1
+}
 
-    let fuel_cost = |a: isize, b: isize| {
-        let n = (a - b).abs();
-        n * (n + 1) / 2
+pub fn solve_part_one(input: &[String]) -> isize {
+    solve_with_fuel(input, Fuel::Linear)
+}
+
+pub fn solve_part_two(input: &[String]) -> isize {
+    solve_with_fuel(input, Fuel::Triangular)
+}
+
+#[test]
+fn test_alignments_match_brute_force() {
+    let brute_force = |crabs: &[isize], fuel: &dyn Fn(isize) -> isize| {
+        let (low, high) = (*crabs.iter().min().unwrap(), *crabs.iter().max().unwrap());
+        (low..=high)
+            .map(|position| total_fuel(crabs, position, fuel))
+            .min()
+            .unwrap()
     };
+    let example = [16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+    assert_eq!((2, 37), align_linear(&example));
+    assert_eq!((5, 168), align_triangular(&example));
 
-    (0..=*max_position)
-        .map(|i| crab_positions.iter().map(|&x| fuel_cost(x, i)).sum())
-        .min()
-        .unwrap()
+    let mut rng = Rng::new(7);
+    for size in 1..50 {
+        let crabs = parse_positions(&generate(&mut rng, size));
+        let cubed = |d: isize| d * d * d;
+        assert_eq!(brute_force(&crabs, &|d| d), align_linear(&crabs).1);
+        assert_eq!(brute_force(&crabs, &|d| d), align_convex(&crabs, |d| d).1);
+        assert_eq!(brute_force(&crabs, &triangular), align_triangular(&crabs).1);
+        assert_eq!(
+            brute_force(&crabs, &triangular),
+            align_convex(&crabs, triangular).1
+        );
+        assert_eq!(brute_force(&crabs, &cubed), align_convex(&crabs, cubed).1);
+        assert_eq!(brute_force(&crabs, &cubed), Fuel::Power(3).align(&crabs).1);
+    }
 }
 
 #[test]
-fn examples_part_two() {
-    assert_eq!(168, solve_part_two(&["16,1,2,0,4,2,7,1,2,14".to_string(),]));
+fn test_solve_with_fuel() {
+    let example = ["16,1,2,0,4,2,7,1,2,14".to_string()];
+    assert_eq!(37, solve_with_fuel(&example, Fuel::Power(1)));
+    // Squared distances are least around the mean of 4.9
+    assert_eq!(
+        total_fuel(&parse_positions(&example), 5, |d| d * d),
+        solve_with_fuel(&example, Fuel::Power(2))
+    );
+}
+
+/// `size` crab positions, spread over about twice as many positions as there are crabs
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let furthest = (size * 2).max(10);
+    vec![(0..size.max(1))
+        .map(|_| rng.below(furthest).to_string())
+        .collect::<Vec<String>>()
+        .join(",")]
 }
@@ -1,4 +1,4 @@
-use util;
+use runner::{Input, Solution};
 mod day1;
 mod day2;
 mod day3;
@@ -7,48 +7,70 @@ mod day5;
 mod day6;
 mod day7;
 
-pub fn aoc2021() {
-    println!("Advent of Code 2021");
-
-    println!(
-        "\tDay 1: Part One={}, Part Two={}",
-        day1::solve_part_one(&util::read_file_input("resources/2021/day1.txt")),
-        day1::solve_part_two(&util::read_file_input("resources/2021/day1.txt")),
-    );
-
-    println!(
-        "\tDay 2: Part One={}, Part Two={}",
-        day2::solve_part_one(&util::read_file_input("resources/2021/day2.txt")),
-        day2::solve_part_two(&util::read_file_input("resources/2021/day2.txt")),
-    );
-
-    println!(
-        "\tDay 3: Part One={}, Part Two={}",
-        day3::solve_part_one(&util::read_file_input("resources/2021/day3.txt")),
-        day3::solve_part_two(&util::read_file_input("resources/2021/day3.txt")),
-    );
-
-    println!(
-        "\tDay 4: Part One={}, Part Two={}",
-        day4::solve_part_one(&util::read_file_input("resources/2021/day4.txt")),
-        day4::solve_part_two(&util::read_file_input("resources/2021/day4.txt")),
-    );
-
-    println!(
-        "\tDay 5: Part One={}, Part Two={}",
-        day5::solve_part_one(&util::read_file_input("resources/2021/day5.txt")),
-        day5::solve_part_two(&util::read_file_input("resources/2021/day5.txt")),
-    );
-
-    println!(
-        "\tDay 6: Part One={}, Part Two={}",
-        day6::solve_part_one(&util::read_file_input("resources/2021/day6.txt")),
-        day6::solve_part_two(&util::read_file_input("resources/2021/day6.txt")),
-    );
-
-    println!(
-        "\tDay 7: Part One={}, Part Two={}",
-        day7::solve_part_one(&util::read_file_input("resources/2021/day7.txt")),
-        day7::solve_part_two(&util::read_file_input("resources/2021/day7.txt")),
-    );
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution {
+            year: 2021,
+            day: 1,
+            input: Input::Lines("resources/2021/day1.txt"),
+            part_one: |i, _| day1::solve_part_one(i).to_string(),
+            part_two: |i, _| day1::solve_part_two(i).to_string(),
+            generate: day1::generate,
+        },
+        Solution {
+            year: 2021,
+            day: 2,
+            input: Input::Lines("resources/2021/day2.txt"),
+            part_one: |i, _| day2::solve_part_one(i).to_string(),
+            part_two: |i, _| day2::solve_part_two(i).to_string(),
+            generate: day2::generate,
+        },
+        Solution {
+            year: 2021,
+            day: 3,
+            input: Input::Lines("resources/2021/day3.txt"),
+            part_one: |i, _| day3::solve_part_one(i).to_string(),
+            part_two: |i, _| day3::solve_part_two(i).to_string(),
+            generate: day3::generate,
+        },
+        Solution {
+            year: 2021,
+            day: 4,
+            input: Input::Lines("resources/2021/day4.txt"),
+            part_one: |i, _| day4::solve_part_one(i).to_string(),
+            part_two: |i, _| day4::solve_part_two(i).to_string(),
+            generate: day4::generate,
+        },
+        Solution {
+            year: 2021,
+            day: 5,
+            input: Input::Lines("resources/2021/day5.txt"),
+            part_one: |i, _| day5::solve_part_one(i).to_string(),
+            part_two: |i, _| day5::solve_part_two(i).to_string(),
+            generate: day5::generate,
+        },
+        Solution {
+            year: 2021,
+            day: 6,
+            input: Input::Lines("resources/2021/day6.txt"),
+            part_one: |i, _| day6::solve_part_one(i).to_string(),
+            part_two: |i, _| day6::solve_part_two(i).to_string(),
+            generate: day6::generate,
+        },
+        Solution {
+            year: 2021,
+            day: 7,
+            input: Input::Lines("resources/2021/day7.txt"),
+            part_one: |i, _| day7::solve_part_one(i).to_string(),
+            // `--param power=N` burns the distance to the power N in fuel instead, for any convex cost
+            part_two: |i, p| {
+                match p.get("power", 0) {
+                    0 => day7::solve_part_two(i),
+                    power => day7::solve_with_fuel(i, day7::Fuel::Power(power as u32)),
+                }
+                .to_string()
+            },
+            generate: day7::generate,
+        },
+    ]
 }
@@ -1,3 +1,5 @@
+use rng::Rng;
+
 /// --- Day 1: Calorie Counting ---
 ///
 /// Santa's reindeer typically eat regular reindeer food, but they need a lot of magical energy to
@@ -90,29 +92,6 @@ pub fn solve_part_one(input: &[String]) -> usize {
         .unwrap_or(&0)
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        24000,
-        solve_part_one(&[
-            "1000".to_string(),
-            "2000".to_string(),
-            "3000".to_string(),
-            "".to_string(),
-            "4000".to_string(),
-            "".to_string(),
-            "5000".to_string(),
-            "6000".to_string(),
-            "".to_string(),
-            "7000".to_string(),
-            "8000".to_string(),
-            "9000".to_string(),
-            "".to_string(),
-            "10000".to_string(),
-        ])
-    );
-}
-
 pub fn solve_part_two(input: &[String]) -> usize {
     let mut elf_snacks = input.iter().fold(Vec::new(), |mut accum, item| {
         if item.is_empty() {
@@ -129,25 +108,17 @@ pub fn solve_part_two(input: &[String]) -> usize {
     elf_snacks.iter().take(3).sum::<usize>()
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        45000,
-        solve_part_two(&[
-            "1000".to_string(),
-            "2000".to_string(),
-            "3000".to_string(),
-            "".to_string(),
-            "4000".to_string(),
-            "".to_string(),
-            "5000".to_string(),
-            "6000".to_string(),
-            "".to_string(),
-            "7000".to_string(),
-            "8000".to_string(),
-            "9000".to_string(),
-            "".to_string(),
-            "10000".to_string(),
-        ])
-    );
+/// Snacks carried by `size` elves, a blank line between each elf's
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    let mut lines = vec![];
+    for elf in 0..size.max(1) {
+        if elf > 0 {
+            lines.push(String::new());
+        }
+        for _ in 0..rng.range(1, 15) {
+            lines.push(rng.range(1000, 60000).to_string());
+        }
+    }
+
+    lines
 }
@@ -1,16 +1,13 @@
-use util;
+use runner::{Input, Solution};
 mod day1;
 
-pub fn aoc2022() {
-    println!("Advent of Code 2022");
-
-    println!(
-        "\tDay 1: Part One={}, Part Two={}",
-        day1::solve_part_one(&util::read_file_input_incl_blanks(
-            "resources/2022/day1.txt"
-        )),
-        day1::solve_part_two(&util::read_file_input_incl_blanks(
-            "resources/2022/day1.txt"
-        )),
-    );
+pub fn solutions() -> Vec<Solution> {
+    vec![Solution {
+        year: 2022,
+        day: 1,
+        input: Input::LinesInclBlanks("resources/2022/day1.txt"),
+        part_one: |i, _| day1::solve_part_one(i).to_string(),
+        part_two: |i, _| day1::solve_part_two(i).to_string(),
+        generate: day1::generate,
+    }]
 }
@@ -1,5 +1,6 @@
 use aho_corasick::AhoCorasick;
 use phf::phf_map;
+use rng::Rng;
 
 /// --- Day 1: Trebuchet?! ---
 ///
@@ -102,35 +103,6 @@ pub fn solve_part_two(input: &[String]) -> usize {
         .sum()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        142,
-        solve_part_one(&[
-            "1abc2".to_string(),
-            "pqr3stu8vwx".to_string(),
-            "a1b2c3d4e5f".to_string(),
-            "treb7uchet".to_string(),
-        ])
-    );
-}
-
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        281,
-        solve_part_two(&[
-            "two1nine".to_string(),
-            "eightwothree".to_string(),
-            "abcone2threexyz".to_string(),
-            "xtwone3four".to_string(),
-            "4nineeightseven2".to_string(),
-            "zoneight234".to_string(),
-            "7pqrstsixteen".to_string(),
-        ])
-    );
-}
-
 #[test]
 fn word_overlaps() {
     assert_eq!(21, solve_part_two(&["twone".to_string()]));
@@ -142,3 +114,27 @@ fn word_overlaps() {
     assert_eq!(98, solve_part_two(&["nineight".to_string()]));
     assert_eq!(79, solve_part_two(&["sevenine".to_string()]));
 }
+
+/// `size` calibration lines of letters, spelled out digits and digits, with at least one actual
+/// digit on every line
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    (0..size.max(1))
+        .map(|_| {
+            let mut parts: Vec<String> = (0..rng.range(0, 6))
+                .map(|_| match rng.below(3) {
+                    0 => rng.choose(&WORDS).to_string(),
+                    1 => rng.range(1, 9).to_string(),
+                    _ => char::from(b'a' + rng.below(26) as u8).to_string(),
+                })
+                .collect();
+            let digit_at = rng.below(parts.len() + 1);
+            parts.insert(digit_at, rng.range(1, 9).to_string());
+
+            parts.concat()
+        })
+        .collect()
+}
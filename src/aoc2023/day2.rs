@@ -1,10 +1,11 @@
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, digit1};
-use nom::combinator::map_res;
+use nom::character::complete::char;
 use nom::multi::separated_list0;
 use nom::sequence::separated_pair;
 use nom::IResult;
+use parse::{parse_line, unsigned};
+use rng::Rng;
 
 /// --- Day 2: Cube Conundrum ---
 ///
@@ -123,7 +124,7 @@ fn round_parser(input: &str) -> IResult<&str, Round> {
     let (input, cubes) = separated_list0(
         tag(", "),
         separated_pair(
-            map_res(digit1, str::parse::<usize>),
+            unsigned::<usize>,
             char(' '),
             alt((tag("red"), tag("green"), tag("blue"))),
         ),
@@ -148,7 +149,7 @@ fn round_parser(input: &str) -> IResult<&str, Round> {
 
 fn game_parser(input: &str) -> IResult<&str, Game> {
     let (input, _) = tag("Game ")(input)?;
-    let (input, id) = map_res(digit1, str::parse)(input)?;
+    let (input, id) = unsigned(input)?;
     let (input, _) = tag(": ")(input)?;
     let (input, rounds) = separated_list0(tag("; "), round_parser)(input)?;
 
@@ -156,7 +157,7 @@ fn game_parser(input: &str) -> IResult<&str, Game> {
 }
 
 fn parse_game(input: &str) -> Option<Game> {
-    game_parser(input).map(|(_, g)| g).ok()
+    parse_line(input, game_parser).ok()
 }
 
 #[test]
@@ -215,20 +216,6 @@ pub fn solve_part_one(input: &[String]) -> usize {
         .sum()
 }
 
-#[test]
-fn examples_part_one() {
-    assert_eq!(
-        8,
-        solve_part_one(&[
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string(),
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue".to_string(),
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red".to_string(),
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red".to_string(),
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".to_string(),
-        ])
-    );
-}
-
 pub fn solve_part_two(input: &[String]) -> usize {
     input
         .iter()
@@ -238,16 +225,23 @@ pub fn solve_part_two(input: &[String]) -> usize {
         .sum()
 }
 
-#[test]
-fn examples_part_two() {
-    assert_eq!(
-        2286,
-        solve_part_two(&[
-            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".to_string(),
-            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue".to_string(),
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red".to_string(),
-            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red".to_string(),
-            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green".to_string(),
-        ])
-    );
+/// `size` games of up to six rounds, with cube counts that make roughly half the games possible
+pub fn generate(rng: &mut Rng, size: usize) -> Vec<String> {
+    (1..=size.max(1))
+        .map(|id| {
+            let rounds: Vec<String> = (0..rng.range(1, 6))
+                .map(|_| {
+                    let mut colours = ["red", "green", "blue"];
+                    rng.shuffle(&mut colours);
+                    colours[..rng.range(1, 3) as usize]
+                        .iter()
+                        .map(|colour| format!("{} {}", rng.range(1, 16), colour))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                })
+                .collect();
+
+            format!("Game {}: {}", id, rounds.join("; "))
+        })
+        .collect()
 }
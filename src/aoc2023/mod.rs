@@ -1,19 +1,24 @@
-use util;
+use runner::{Input, Solution};
 mod day1;
 mod day2;
 
-pub fn aoc2023() {
-    println!("Advent of Code 2023");
-
-    println!(
-        "\tDay 1: Part One={}, Part Two={}",
-        day1::solve_part_one(&util::read_file_input("resources/2023/day1.txt")),
-        day1::solve_part_two(&util::read_file_input("resources/2023/day1.txt")),
-    );
-
-    println!(
-        "\tDay 2: Part One={}, Part Two={}",
-        day2::solve_part_one(&util::read_file_input("resources/2023/day2.txt")),
-        day2::solve_part_two(&util::read_file_input("resources/2023/day2.txt")),
-    );
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        Solution {
+            year: 2023,
+            day: 1,
+            input: Input::Lines("resources/2023/day1.txt"),
+            part_one: |i, _| day1::solve_part_one(i).to_string(),
+            part_two: |i, _| day1::solve_part_two(i).to_string(),
+            generate: day1::generate,
+        },
+        Solution {
+            year: 2023,
+            day: 2,
+            input: Input::Lines("resources/2023/day2.txt"),
+            part_one: |i, _| day2::solve_part_one(i).to_string(),
+            part_two: |i, _| day2::solve_part_two(i).to_string(),
+            generate: day2::generate,
+        },
+    ]
 }
@@ -0,0 +1,309 @@
+use cycle::{Cycle, Detector};
+use grid::Grid;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::mem;
+
+/// Anything that evolves a generation at a time by applying a rule to every cell at once
+pub trait Automaton {
+    /// Enough of the state to tell whether two generations are identical
+    type Snapshot: Hash + Eq;
+
+    /// Move on a generation, returning whether anything changed
+    fn step(&mut self) -> bool;
+
+    /// How many generations have been stepped through so far
+    fn generation(&self) -> usize;
+
+    fn snapshot(&self) -> Self::Snapshot;
+
+    /// Step until a generation changes nothing, returning the generation that first reached that
+    /// state, or `None` if it still hasn't settled after `max_generations`
+    fn run_to_fixed_point(&mut self, max_generations: usize) -> Option<usize> {
+        while self.generation() < max_generations {
+            if !self.step() {
+                return Some(self.generation() - 1);
+            }
+        }
+        None
+    }
+
+    /// Step until a generation repeats an earlier one, or `None` if that doesn't happen within
+    /// `max_generations`
+    ///
+    /// Every generation is remembered, so this is for automata that are expected to repeat fairly
+    /// quickly. `run_to_fixed_point` is cheaper when only a fixed point is expected.
+    fn find_cycle(&mut self, max_generations: usize) -> Option<Cycle> {
+        let first = self.generation();
+        let mut detector = Detector::new();
+        loop {
+            if let Some(cycle) = detector.observe(self.snapshot()) {
+                return Some(Cycle {
+                    start: first + cycle.start,
+                    period: cycle.period,
+                });
+            }
+            if self.generation() >= max_generations {
+                return None;
+            }
+            self.step();
+        }
+    }
+}
+
+/// Summarises the cells around a position that a rule cares about, such as counting how many
+/// neighbours are alive
+pub type GridNeighbourhood<T, S> = Box<dyn Fn(&Grid<T>, usize, usize) -> S>;
+
+/// Works out a cell's next state from its current one and its neighbourhood's summary
+pub type GridRule<T, S> = Box<dyn Fn(&T, S) -> T>;
+
+/// An automaton over every cell of a fixed size grid
+///
+/// Two grids are kept and swapped between generations, so stepping doesn't allocate.
+pub struct GridAutomaton<T, S> {
+    current: Grid<T>,
+    next: Grid<T>,
+    generation: usize,
+    neighbourhood: GridNeighbourhood<T, S>,
+    rule: GridRule<T, S>,
+}
+
+impl<T: Clone + PartialEq, S> GridAutomaton<T, S> {
+    pub fn new(
+        initial: Grid<T>,
+        neighbourhood: GridNeighbourhood<T, S>,
+        rule: GridRule<T, S>,
+    ) -> GridAutomaton<T, S> {
+        GridAutomaton {
+            next: initial.clone(),
+            current: initial,
+            generation: 0,
+            neighbourhood,
+            rule,
+        }
+    }
+
+    pub fn state(&self) -> &Grid<T> {
+        &self.current
+    }
+}
+
+impl<T: Clone + Eq + Hash, S> Automaton for GridAutomaton<T, S> {
+    type Snapshot = Grid<T>;
+
+    fn step(&mut self) -> bool {
+        let mut changed = false;
+        for y in 0..self.current.height() {
+            for x in 0..self.current.width() {
+                let summary = (self.neighbourhood)(&self.current, x, y);
+                let cell = (self.rule)(&self.current[(x, y)], summary);
+                changed |= cell != self.current[(x, y)];
+                self.next[(x, y)] = cell;
+            }
+        }
+
+        mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+        changed
+    }
+
+    fn generation(&self) -> usize {
+        self.generation
+    }
+
+    fn snapshot(&self) -> Grid<T> {
+        self.current.clone()
+    }
+}
+
+/// The positions whose live cells count towards a position's neighbours
+#[allow(dead_code)]
+pub type SparseNeighbourhood<P> = Box<dyn Fn(&P) -> Vec<P>>;
+
+/// Whether a cell is alive next generation, from whether it's alive now and its live neighbours
+#[allow(dead_code)]
+pub type SparseRule = Box<dyn Fn(bool, usize) -> bool>;
+
+/// An automaton over an unbounded space where only the live cells are stored, so it can grow in
+/// any direction and in any number of dimensions
+#[allow(dead_code)]
+pub struct SparseAutomaton<P> {
+    live: HashSet<P>,
+    next: HashSet<P>,
+    live_neighbours: HashMap<P, usize>,
+    generation: usize,
+    neighbourhood: SparseNeighbourhood<P>,
+    rule: SparseRule,
+}
+
+#[allow(dead_code)]
+impl<P: Copy + Hash + Eq> SparseAutomaton<P> {
+    pub fn new<I>(
+        live: I,
+        neighbourhood: SparseNeighbourhood<P>,
+        rule: SparseRule,
+    ) -> SparseAutomaton<P>
+    where
+        I: IntoIterator<Item = P>,
+    {
+        SparseAutomaton {
+            live: live.into_iter().collect(),
+            next: HashSet::new(),
+            live_neighbours: HashMap::new(),
+            generation: 0,
+            neighbourhood,
+            rule,
+        }
+    }
+
+    pub fn live(&self) -> &HashSet<P> {
+        &self.live
+    }
+}
+
+impl<P: Copy + Hash + Ord> Automaton for SparseAutomaton<P> {
+    type Snapshot = Vec<P>;
+
+    fn step(&mut self) -> bool {
+        self.live_neighbours.clear();
+        for cell in &self.live {
+            for neighbour in (self.neighbourhood)(cell) {
+                *self.live_neighbours.entry(neighbour).or_insert(0) += 1;
+            }
+        }
+
+        self.next.clear();
+        for (cell, count) in &self.live_neighbours {
+            if (self.rule)(self.live.contains(cell), *count) {
+                self.next.insert(*cell);
+            }
+        }
+        // Live cells with no live neighbours never got counted above
+        for cell in &self.live {
+            if !self.live_neighbours.contains_key(cell) && (self.rule)(true, 0) {
+                self.next.insert(*cell);
+            }
+        }
+
+        let changed = self.next != self.live;
+        mem::swap(&mut self.live, &mut self.next);
+        self.generation += 1;
+        changed
+    }
+
+    fn generation(&self) -> usize {
+        self.generation
+    }
+
+    fn snapshot(&self) -> Vec<P> {
+        let mut live: Vec<P> = self.live.iter().cloned().collect();
+        live.sort();
+        live
+    }
+}
+
+/// Every position touching `p`, including diagonally, in any number of dimensions
+#[allow(dead_code)]
+pub fn moore_neighbours<const N: usize>(p: &[isize; N]) -> Vec<[isize; N]> {
+    let mut neighbours = vec![*p];
+    for axis in 0..N {
+        neighbours = neighbours
+            .iter()
+            .flat_map(|n| {
+                (-1..=1).map(move |d| {
+                    let mut moved = *n;
+                    moved[axis] += d;
+                    moved
+                })
+            })
+            .collect();
+    }
+    neighbours.retain(|n| n != p);
+    neighbours
+}
+
+#[cfg(test)]
+fn life(live: &[[isize; 2]]) -> SparseAutomaton<[isize; 2]> {
+    SparseAutomaton::new(
+        live.iter().cloned(),
+        Box::new(moore_neighbours),
+        Box::new(|alive, neighbours| neighbours == 3 || (alive && neighbours == 2)),
+    )
+}
+
+#[test]
+fn test_moore_neighbours() {
+    assert_eq!(8, moore_neighbours(&[0, 0]).len());
+    assert_eq!(26, moore_neighbours(&[0, 0, 0]).len());
+    assert_eq!(80, moore_neighbours(&[5, -5, 0, 1]).len());
+    assert!(moore_neighbours(&[1, 2, 3]).contains(&[0, 3, 3]));
+}
+
+#[test]
+fn test_sparse_fixed_point() {
+    let mut block = life(&[[0, 0], [0, 1], [1, 0], [1, 1]]);
+    assert_eq!(Some(0), block.run_to_fixed_point(10));
+
+    // Three in an L become a block after one generation
+    let mut corner = life(&[[0, 0], [0, 1], [1, 0]]);
+    assert_eq!(Some(1), corner.run_to_fixed_point(10));
+    assert_eq!(4, corner.live().len());
+}
+
+#[test]
+fn test_sparse_cycle() {
+    let mut blinker = life(&[[0, -1], [0, 0], [0, 1]]);
+    assert_eq!(None, blinker.run_to_fixed_point(10));
+
+    let mut blinker = life(&[[0, -1], [0, 0], [0, 1]]);
+    assert_eq!(
+        Some(Cycle {
+            start: 0,
+            period: 2
+        }),
+        blinker.find_cycle(10)
+    );
+
+    // A glider never repeats, it just keeps on moving
+    let mut glider = life(&[[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]]);
+    assert_eq!(None, glider.find_cycle(20));
+    assert_eq!(20, glider.generation());
+}
+
+#[test]
+fn test_grid_automaton() {
+    // Each cell copies the one to its left, so a light moves right until it falls off the edge
+    let lights = Grid::parse(&["#...".to_string()], |c| c == '#');
+    let mut automaton = GridAutomaton::new(
+        lights,
+        Box::new(|grid: &Grid<bool>, x, y| *grid.get(x as isize - 1, y as isize).unwrap_or(&false)),
+        Box::new(|_, left| left),
+    );
+
+    assert!(automaton.step());
+    assert_eq!(
+        ".#..",
+        automaton
+            .state()
+            .map(|l| if *l { '#' } else { '.' })
+            .to_string()
+    );
+    assert_eq!(Some(4), automaton.run_to_fixed_point(10));
+    assert_eq!(5, automaton.generation());
+
+    // Wrapping round, the light never settles but comes back round every 4 generations
+    let lights = Grid::parse(&["..#.".to_string()], |c| c == '#').wrapping(true, false);
+    let mut automaton = GridAutomaton::new(
+        lights,
+        Box::new(|grid: &Grid<bool>, x, y| *grid.get(x as isize - 1, y as isize).unwrap()),
+        Box::new(|_, left| left),
+    );
+    assert_eq!(
+        Some(Cycle {
+            start: 0,
+            period: 4
+        }),
+        automaton.find_cycle(10)
+    );
+}
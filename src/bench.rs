@@ -0,0 +1,598 @@
+use chrono::Utc;
+use generate::Generated;
+use runner::{self, Params, Solution};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{self, Command};
+use std::time::{Duration, Instant};
+
+const DEFAULT_HISTORY: &str = "target/bench-history.tsv";
+const DEFAULT_RUNS: usize = 5;
+const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+/// Input label for timings taken against the real puzzle input
+const REAL_INPUT: &str = "real";
+
+type PartKey = (i32, u32, u8);
+
+/// Command line options shared by `bench` and `compare`
+///
+/// Positional arguments narrow down the year and day, flags tune the measurement. Giving `--size`
+/// or `--seed` times the solutions against generated input rather than the real puzzle input, and
+/// `--param` runs them with different parameters:
+///
+/// ```text
+/// bench [year] [day] [--runs N] [--history PATH] [--size N] [--seed S] [--param name=value]...
+/// compare [year] [day] [--runs N] [--history PATH] [--size N] [--seed S] [--param name=value]...
+///         [--baseline REV] [--threshold PERCENT]
+/// ```
+#[derive(Debug, PartialEq)]
+struct Options {
+    year: Option<i32>,
+    day: Option<u32>,
+    runs: usize,
+    history: String,
+    baseline: Option<String>,
+    threshold: f64,
+    generated: Option<Generated>,
+    params: Params,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Options {
+        let mut options = Options {
+            year: None,
+            day: None,
+            runs: DEFAULT_RUNS,
+            history: DEFAULT_HISTORY.to_string(),
+            baseline: None,
+            threshold: DEFAULT_THRESHOLD_PERCENT,
+            generated: None,
+            params: Params::default(),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .unwrap_or_else(|| panic!("Missing value for {}", arg))
+                    .to_string()
+            };
+            match arg.as_str() {
+                "--runs" => options.runs = value().parse().unwrap(),
+                "--history" => options.history = value(),
+                "--baseline" => options.baseline = Some(value()),
+                "--threshold" => options.threshold = value().parse().unwrap(),
+                "--param" => options.params.set(&value()),
+                "--size" => {
+                    options
+                        .generated
+                        .get_or_insert_with(Generated::default)
+                        .size = value().parse().unwrap()
+                }
+                "--seed" => {
+                    options
+                        .generated
+                        .get_or_insert_with(Generated::default)
+                        .seed = value().parse().unwrap()
+                }
+                positional if options.year.is_none() => {
+                    options.year = Some(positional.parse().unwrap())
+                }
+                positional if options.day.is_none() => {
+                    options.day = Some(positional.parse().unwrap())
+                }
+                unknown => panic!("Unexpected argument: {}", unknown),
+            }
+        }
+
+        assert!(options.runs > 0, "Need at least one run to time anything");
+        options
+    }
+
+    /// What the solutions were run against, so timings are only compared with others taken the same
+    /// way, including any parameters as they can change how much work there is
+    fn input_label(&self) -> String {
+        let input = self.generated.map_or(REAL_INPUT.to_string(), |g| g.label());
+        match self.params.to_string().as_str() {
+            "" => input,
+            params => format!("{}:{}", input, params),
+        }
+    }
+}
+
+/// Median wall-clock time of a single part over several runs
+#[derive(Debug, PartialEq)]
+pub struct Timing {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+    pub median: Duration,
+    pub runs: usize,
+}
+
+impl Timing {
+    fn key(&self) -> PartKey {
+        (self.year, self.day, self.part)
+    }
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    let mid = samples.len() / 2;
+    if samples.len().is_multiple_of(2) {
+        (samples[mid - 1] + samples[mid]) / 2
+    } else {
+        samples[mid]
+    }
+}
+
+/// Time every part of the given solutions, loading or generating each input once outside of the
+/// timed region
+pub fn measure(
+    solutions: &[Solution],
+    runs: usize,
+    generated: Option<Generated>,
+    params: &Params,
+) -> Vec<Timing> {
+    let mut timings = vec![];
+
+    for solution in solutions {
+        let input = solution.load_input(generated);
+        for (part, solve) in solution.parts().iter() {
+            let samples = (0..runs)
+                .map(|_| {
+                    let start = Instant::now();
+                    solve(&input, params);
+                    start.elapsed()
+                })
+                .collect();
+
+            timings.push(Timing {
+                year: solution.year,
+                day: solution.day,
+                part: *part,
+                median: median(samples),
+                runs,
+            });
+        }
+    }
+
+    timings
+}
+
+/// One line of the history file, tab separated in field order
+#[derive(Debug, PartialEq)]
+struct Record {
+    revision: String,
+    machine: String,
+    recorded_at: String,
+    year: i32,
+    day: u32,
+    part: u8,
+    median_nanos: u128,
+    runs: usize,
+    input: String,
+}
+
+impl Record {
+    fn from_timing(
+        timing: &Timing,
+        revision: &str,
+        machine: &str,
+        recorded_at: &str,
+        input: &str,
+    ) -> Record {
+        Record {
+            revision: revision.to_string(),
+            machine: machine.to_string(),
+            recorded_at: recorded_at.to_string(),
+            year: timing.year,
+            day: timing.day,
+            part: timing.part,
+            median_nanos: timing.median.as_nanos(),
+            runs: timing.runs,
+            input: input.to_string(),
+        }
+    }
+
+    /// Lines recorded before inputs were labelled have no input field, and were all timed against
+    /// the real input
+    fn parse(line: &str) -> Option<Record> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 8 && fields.len() != 9 {
+            return None;
+        }
+
+        Some(Record {
+            revision: fields[0].to_string(),
+            machine: fields[1].to_string(),
+            recorded_at: fields[2].to_string(),
+            year: fields[3].parse().ok()?,
+            day: fields[4].parse().ok()?,
+            part: fields[5].parse().ok()?,
+            median_nanos: fields[6].parse().ok()?,
+            runs: fields[7].parse().ok()?,
+            input: fields.get(8).unwrap_or(&REAL_INPUT).to_string(),
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.revision,
+            self.machine,
+            self.recorded_at,
+            self.year,
+            self.day,
+            self.part,
+            self.median_nanos,
+            self.runs,
+            self.input
+        )
+    }
+
+    fn key(&self) -> PartKey {
+        (self.year, self.day, self.part)
+    }
+}
+
+fn load_history(path: &str) -> Vec<Record> {
+    match File::open(path) {
+        Ok(f) => BufReader::new(f)
+            .lines()
+            .map(|l| l.unwrap())
+            .filter_map(|l| Record::parse(&l))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+fn append_history(path: &str, records: &[Record]) {
+    let mut f = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap_or_else(|e| panic!("Could not open history file {}: {}", path, e));
+    for record in records {
+        writeln!(f, "{}", record.to_line()).unwrap();
+    }
+}
+
+fn run_command(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// Short git hash of HEAD, marked dirty when the working tree has uncommitted changes
+fn current_revision() -> String {
+    match run_command("git", &["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match run_command("git", &["status", "--porcelain"]) {
+            Some(_) => format!("{}-dirty", hash),
+            None => hash,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// Host name and CPU architecture, so timings from different machines are never compared
+fn current_machine() -> String {
+    let host = std::env::var("HOSTNAME")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .or_else(|| run_command("hostname", &[]))
+        .or_else(|| {
+            std::fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|h| h.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    format!("{}-{}", host, std::env::consts::ARCH)
+}
+
+/// The most recently recorded revision on this machine and input other than the current one
+fn latest_other_revision(
+    history: &[Record],
+    machine: &str,
+    input: &str,
+    current: &str,
+) -> Option<String> {
+    history
+        .iter()
+        .rev()
+        .find(|r| r.machine == machine && r.input == input && r.revision != current)
+        .map(|r| r.revision.clone())
+}
+
+/// Median of all recorded medians per part for a revision, so repeated bench runs smooth out noise
+fn baseline_medians(
+    history: &[Record],
+    machine: &str,
+    input: &str,
+    revision: &str,
+) -> HashMap<PartKey, Duration> {
+    let mut samples: HashMap<PartKey, Vec<Duration>> = HashMap::new();
+    history
+        .iter()
+        .filter(|r| r.machine == machine && r.input == input && r.revision == revision)
+        .for_each(|r| {
+            samples
+                .entry(r.key())
+                .or_default()
+                .push(Duration::from_nanos(r.median_nanos as u64))
+        });
+
+    samples
+        .into_iter()
+        .map(|(key, durations)| (key, median(durations)))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum Verdict {
+    New,
+    Faster,
+    Unchanged,
+    Slower,
+}
+
+fn judge(baseline: Option<Duration>, current: Duration, threshold_percent: f64) -> Verdict {
+    match baseline {
+        None => Verdict::New,
+        Some(baseline) => {
+            let ratio = current.as_secs_f64() / baseline.as_secs_f64().max(f64::MIN_POSITIVE);
+            let threshold = threshold_percent / 100.0;
+            if ratio > 1.0 + threshold {
+                Verdict::Slower
+            } else if ratio < 1.0 - threshold {
+                Verdict::Faster
+            } else {
+                Verdict::Unchanged
+            }
+        }
+    }
+}
+
+fn print_timings(timings: &[Timing]) {
+    println!(
+        "{:>4} {:>3} {:>4} {:>14} {:>4}",
+        "Year", "Day", "Part", "Median", "Runs"
+    );
+    for timing in timings {
+        println!(
+            "{:>4} {:>3} {:>4} {:>14} {:>4}",
+            timing.year,
+            timing.day,
+            timing.part,
+            format!("{:?}", timing.median),
+            timing.runs
+        );
+    }
+}
+
+/// Time the selected solutions and append the results to the history file
+pub fn bench(args: &[String]) {
+    let options = Options::parse(args);
+    let input = options.input_label();
+    let timings = measure(
+        &runner::select(options.year, options.day),
+        options.runs,
+        options.generated,
+        &options.params,
+    );
+    print_timings(&timings);
+
+    let revision = current_revision();
+    let machine = current_machine();
+    let recorded_at = Utc::now().to_rfc3339();
+    let records: Vec<Record> = timings
+        .iter()
+        .map(|t| Record::from_timing(t, &revision, &machine, &recorded_at, &input))
+        .collect();
+    append_history(&options.history, &records);
+
+    println!(
+        "Recorded {} timings for {} on {} with {} input in {}",
+        records.len(),
+        revision,
+        machine,
+        input,
+        options.history
+    );
+}
+
+/// Time the selected solutions against a stored baseline, exiting non-zero if any part got slower
+pub fn compare(args: &[String]) {
+    let options = Options::parse(args);
+    let history = load_history(&options.history);
+    let machine = current_machine();
+    let input = options.input_label();
+
+    let baseline_revision = options
+        .baseline
+        .clone()
+        .or_else(|| latest_other_revision(&history, &machine, &input, &current_revision()))
+        .unwrap_or_else(|| {
+            panic!(
+                "No baseline recorded on {} with {} input in {}, run bench first",
+                machine, input, options.history
+            )
+        });
+    let baseline = baseline_medians(&history, &machine, &input, &baseline_revision);
+
+    println!(
+        "Comparing against {} on {} with {} input (threshold {}%)",
+        baseline_revision, machine, input, options.threshold
+    );
+    println!(
+        "{:>4} {:>3} {:>4} {:>14} {:>14} {:>8} {:>9}",
+        "Year", "Day", "Part", "Baseline", "Current", "Change", "Verdict"
+    );
+
+    let timings = measure(
+        &runner::select(options.year, options.day),
+        options.runs,
+        options.generated,
+        &options.params,
+    );
+    let mut regressions = 0;
+    for timing in &timings {
+        let previous = baseline.get(&timing.key()).cloned();
+        let verdict = judge(previous, timing.median, options.threshold);
+        if verdict == Verdict::Slower {
+            regressions += 1;
+        }
+
+        println!(
+            "{:>4} {:>3} {:>4} {:>14} {:>14} {:>8} {:>9}",
+            timing.year,
+            timing.day,
+            timing.part,
+            previous.map_or("-".to_string(), |p| format!("{:?}", p)),
+            format!("{:?}", timing.median),
+            previous.map_or("-".to_string(), |p| format!(
+                "{:+.1}%",
+                (timing.median.as_secs_f64() / p.as_secs_f64() - 1.0) * 100.0
+            )),
+            format!("{:?}", verdict)
+        );
+    }
+
+    if regressions > 0 {
+        println!("{} part(s) slower than {}", regressions, baseline_revision);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+fn record(revision: &str, machine: &str, part: u8, median_nanos: u128) -> Record {
+    Record {
+        revision: revision.to_string(),
+        machine: machine.to_string(),
+        recorded_at: "2020-12-15T00:00:00+00:00".to_string(),
+        year: 2020,
+        day: 15,
+        part,
+        median_nanos,
+        runs: 5,
+        input: REAL_INPUT.to_string(),
+    }
+}
+
+#[test]
+fn test_parse_options() {
+    let args: Vec<String> = ["2020", "15", "--runs", "3", "--threshold", "25"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let options = Options::parse(&args);
+
+    assert_eq!(Some(2020), options.year);
+    assert_eq!(Some(15), options.day);
+    assert_eq!(3, options.runs);
+    assert_eq!(25.0, options.threshold);
+    assert_eq!(DEFAULT_HISTORY, options.history);
+    assert_eq!(None, options.generated);
+    assert_eq!("real", options.input_label());
+
+    let args: Vec<String> = ["--seed", "7"].iter().map(|s| s.to_string()).collect();
+    let options = Options::parse(&args);
+    assert_eq!(
+        Some(Generated {
+            seed: 7,
+            ..Generated::default()
+        }),
+        options.generated
+    );
+    assert_eq!("gen:100:7", options.input_label());
+
+    let args: Vec<String> = ["2020", "9", "--param", "preamble=5"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let options = Options::parse(&args);
+    assert_eq!(5, options.params.get("preamble", 25));
+    assert_eq!("real:preamble=5", options.input_label());
+}
+
+#[test]
+fn test_median() {
+    let ms = Duration::from_millis;
+    assert_eq!(ms(3), median(vec![ms(5), ms(1), ms(3)]));
+    assert_eq!(ms(3), median(vec![ms(4), ms(1), ms(2), ms(9)]));
+}
+
+#[test]
+fn test_record_round_trip() {
+    let r = record("abc1234", "box-x86_64", 2, 123456);
+    assert_eq!(
+        Some(r),
+        Record::parse(&record("abc1234", "box-x86_64", 2, 123456).to_line())
+    );
+    assert_eq!(None, Record::parse("not\ta\trecord"));
+
+    let mut generated = record("abc1234", "box-x86_64", 1, 100);
+    generated.input = "gen:100:2020".to_string();
+    assert_eq!(
+        Some(&generated),
+        Record::parse(&generated.to_line()).as_ref()
+    );
+}
+
+#[test]
+fn test_parse_unlabelled_record() {
+    let line = "abc1234\tbox-x86_64\t2020-12-15T00:00:00+00:00\t2020\t15\t2\t123456\t5";
+    assert_eq!(
+        Some(record("abc1234", "box-x86_64", 2, 123456)),
+        Record::parse(line)
+    );
+}
+
+#[test]
+fn test_baseline_selection() {
+    let history = vec![
+        record("aaa", "box", 1, 100),
+        record("aaa", "box", 1, 300),
+        record("aaa", "box", 1, 200),
+        record("bbb", "other-box", 1, 50),
+        record("ccc", "box", 1, 400),
+    ];
+
+    assert_eq!(
+        Some("aaa".to_string()),
+        latest_other_revision(&history, "box", REAL_INPUT, "ccc")
+    );
+    assert_eq!(
+        Some("ccc".to_string()),
+        latest_other_revision(&history, "box", REAL_INPUT, "ddd")
+    );
+    assert_eq!(
+        None,
+        latest_other_revision(&history, "new-box", REAL_INPUT, "ccc")
+    );
+    assert_eq!(
+        None,
+        latest_other_revision(&history, "box", "gen:100:2020", "ccc")
+    );
+
+    let medians = baseline_medians(&history, "box", REAL_INPUT, "aaa");
+    assert_eq!(
+        Some(&Duration::from_nanos(200)),
+        medians.get(&(2020, 15, 1))
+    );
+    assert_eq!(None, medians.get(&(2020, 15, 2)));
+}
+
+#[test]
+fn test_judge() {
+    let ms = Duration::from_millis;
+    assert_eq!(Verdict::New, judge(None, ms(10), 10.0));
+    assert_eq!(Verdict::Unchanged, judge(Some(ms(100)), ms(109), 10.0));
+    assert_eq!(Verdict::Slower, judge(Some(ms(100)), ms(111), 10.0));
+    assert_eq!(Verdict::Faster, judge(Some(ms(100)), ms(89), 10.0));
+}
@@ -0,0 +1,135 @@
+/// A bit mask written out most significant bit first as `0`, `1` and `X`, where `0` and `1` force
+/// a bit to that value and `X` leaves it floating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mask {
+    pub zeros: u64,
+    pub ones: u64,
+    pub floating: u64,
+}
+
+impl Mask {
+    pub fn parse(pattern: &str) -> Mask {
+        assert!(pattern.len() <= 64, "Mask too wide: {}", pattern);
+        let mut mask = Mask {
+            zeros: 0,
+            ones: 0,
+            floating: 0,
+        };
+        for c in pattern.chars() {
+            mask.zeros <<= 1;
+            mask.ones <<= 1;
+            mask.floating <<= 1;
+            match c {
+                '0' => mask.zeros |= 1,
+                '1' => mask.ones |= 1,
+                'X' => mask.floating |= 1,
+                _ => panic!("Invalid mask bit {} in {}", c, pattern),
+            }
+        }
+        mask
+    }
+
+    /// Force the `0` and `1` bits, keeping the value's own bits where the mask floats
+    pub fn apply(&self, value: u64) -> u64 {
+        value & !self.zeros | self.ones
+    }
+
+    /// Every value made by setting the mask's `1` bits, keeping the value's bits under its `0`s,
+    /// and trying both ways for each floating bit
+    pub fn float(&self, value: u64) -> impl Iterator<Item = u64> {
+        let fixed = (value | self.ones) & !self.floating;
+        subsets(self.floating).map(move |bits| fixed | bits)
+    }
+}
+
+/// Every combination of the bits set in `bits`, from none of them up to all of them
+pub fn subsets(bits: u64) -> impl Iterator<Item = u64> {
+    // Subtracting `bits` then masking counts up through the combinations as if the set bits were
+    // the only ones there
+    let mut next = Some(0);
+    std::iter::from_fn(move || {
+        let subset = next?;
+        next = match subset == bits {
+            true => None,
+            false => Some(subset.wrapping_sub(bits) & bits),
+        };
+        Some(subset)
+    })
+}
+
+/// How many of the words have bit `bit` set, counting from the least significant bit
+pub fn count_column(words: &[u64], bit: u32) -> usize {
+    words.iter().filter(|w| *w >> bit & 1 == 1).count()
+}
+
+/// How many of the words have each of the lowest `width` bits set, least significant bit first
+pub fn column_counts(words: &[u64], width: u32) -> Vec<usize> {
+    (0..width).map(|bit| count_column(words, bit)).collect()
+}
+
+/// The lowest `width` bits all set
+pub fn low_bits(width: u32) -> u64 {
+    match width {
+        64 => u64::MAX,
+        _ => (1 << width) - 1,
+    }
+}
+
+/// Read a number written in binary with any alphabet, most significant bit first, where
+/// characters in `zeros` stand for 0 and those in `ones` for 1, like `FBFBBFF` for seat rows
+pub fn decode(text: &str, zeros: &str, ones: &str) -> Option<u64> {
+    text.chars().try_fold(0, |value, c| {
+        if zeros.contains(c) {
+            Some(value << 1)
+        } else if ones.contains(c) {
+            Some(value << 1 | 1)
+        } else {
+            None
+        }
+    })
+}
+
+/// Read a number written in plain `0` and `1` binary, panicking on anything else
+pub fn parse_binary(text: &str) -> u64 {
+    decode(text, "0", "1").unwrap_or_else(|| panic!("Not binary: {}", text))
+}
+
+#[test]
+fn test_mask() {
+    let mask = Mask::parse("X1XX0X");
+    assert_eq!(0b010000, mask.ones);
+    assert_eq!(0b000010, mask.zeros);
+    assert_eq!(0b101101, mask.floating);
+    assert_eq!(0b011101, mask.apply(0b001111));
+
+    let mut floated: Vec<u64> = Mask::parse("X1001X").float(42).collect();
+    floated.sort_unstable();
+    assert_eq!(vec![26, 27, 58, 59], floated);
+}
+
+#[test]
+fn test_subsets() {
+    assert_eq!(vec![0], subsets(0).collect::<Vec<_>>());
+    assert_eq!(
+        vec![0, 0b0010, 0b1000, 0b1010],
+        subsets(0b1010).collect::<Vec<_>>()
+    );
+    assert_eq!(1 << 10, subsets(0b11_1111_1111 << 50).count());
+}
+
+#[test]
+fn test_columns() {
+    let words = [0b00100, 0b11110, 0b10110, 0b10111];
+    assert_eq!(vec![1, 3, 4, 1, 3], column_counts(&words, 5));
+    assert_eq!(3, count_column(&words, 4));
+    assert_eq!(0b11111, low_bits(5));
+    assert_eq!(u64::MAX, low_bits(64));
+}
+
+#[test]
+fn test_decode() {
+    assert_eq!(Some(44), decode("FBFBBFF", "F", "B"));
+    assert_eq!(Some(357), decode("FBFBBFFRLR", "FL", "BR"));
+    assert_eq!(None, decode("FBX", "F", "B"));
+    assert_eq!(22, parse_binary("10110"));
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+
+/// How many times each item has turned up, like a multiset
+///
+/// Items that have never turned up count as zero rather than being missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Hash + Eq> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq> Default for Counter<T> {
+    fn default() -> Counter<T> {
+        Counter::new()
+    }
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    pub fn new() -> Counter<T> {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_many(item, 1);
+    }
+
+    pub fn add_many(&mut self, item: T, count: usize) {
+        if count > 0 {
+            *self.counts.entry(item).or_insert(0) += count;
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).cloned().unwrap_or(0)
+    }
+
+    /// How many distinct items there are
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// How many items there are, counting each time they turned up
+    #[allow(dead_code)]
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Every distinct item with its count, in no particular order
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, count)| (item, *count))
+    }
+
+    /// The items that turned up exactly `count` times
+    pub fn with_count(&self, count: usize) -> impl Iterator<Item = &T> {
+        self.counts
+            .iter()
+            .filter(move |(_, c)| **c == count)
+            .map(|(item, _)| item)
+    }
+
+    /// Add every count from `other` into this one
+    #[allow(dead_code)]
+    pub fn merge(&mut self, other: Counter<T>) {
+        other
+            .counts
+            .into_iter()
+            .for_each(|(item, count)| self.add_many(item, count));
+    }
+}
+
+impl<T: Hash + Eq + Clone> Counter<T> {
+    /// The larger of the two counts for each item, as a multiset union
+    #[allow(dead_code)]
+    pub fn union(&self, other: &Counter<T>) -> Counter<T> {
+        let mut union = self.clone();
+        for (item, count) in other.iter() {
+            let existing = union.counts.entry(item.clone()).or_insert(0);
+            *existing = (*existing).max(count);
+        }
+        union
+    }
+
+    /// The smaller of the two counts for each item, so only items in both are kept
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &Counter<T>) -> Counter<T> {
+        Counter {
+            counts: self
+                .iter()
+                .map(|(item, count)| (item.clone(), count.min(other.get(item))))
+                .filter(|(_, count)| *count > 0)
+                .collect(),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Ord> Counter<T> {
+    /// Every distinct item, most common first, with ties going to the smallest item
+    #[allow(dead_code)]
+    pub fn most_common(&self) -> Vec<(&T, usize)> {
+        let mut items: Vec<(&T, usize)> = self.iter().collect();
+        items.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        items
+    }
+
+    /// Every distinct item, least common first, with ties going to the smallest item
+    #[allow(dead_code)]
+    pub fn least_common(&self) -> Vec<(&T, usize)> {
+        let mut items: Vec<(&T, usize)> = self.iter().collect();
+        items.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+        items
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Counter<T> {
+        let mut counter = Counter::new();
+        counter.extend(items);
+        counter
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        items.into_iter().for_each(|item| self.add(item));
+    }
+}
+
+#[test]
+fn test_counting() {
+    let mut letters: Counter<char> = "bababc".chars().collect();
+    assert_eq!(3, letters.get(&'b'));
+    assert_eq!(0, letters.get(&'z'));
+    assert_eq!(3, letters.len());
+    assert_eq!(6, letters.total());
+    assert_eq!(vec![&'a'], letters.with_count(2).collect::<Vec<_>>());
+
+    letters.merge("zza".chars().collect());
+    assert_eq!(3, letters.get(&'a'));
+    assert_eq!(2, letters.get(&'z'));
+    letters.add_many('q', 0);
+    assert_eq!(4, letters.len());
+}
+
+#[test]
+fn test_most_and_least_common() {
+    let letters: Counter<char> = "abbcccdd".chars().collect();
+    assert_eq!(
+        vec![(&'c', 3), (&'b', 2), (&'d', 2), (&'a', 1)],
+        letters.most_common()
+    );
+    assert_eq!(Some(&(&'a', 1)), letters.least_common().first());
+}
+
+#[test]
+fn test_set_operations() {
+    let a: Counter<char> = "aabc".chars().collect();
+    let b: Counter<char> = "abbd".chars().collect();
+
+    let union = a.union(&b);
+    assert_eq!(
+        vec![(&'a', 2), (&'b', 2), (&'c', 1), (&'d', 1)],
+        union.most_common()
+    );
+
+    let intersection = a.intersection(&b);
+    assert_eq!(vec![(&'a', 1), (&'b', 1)], intersection.most_common());
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence of states started going round in circles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cycle {
+    /// The first state that turned up again later
+    pub start: usize,
+    /// How many steps it took to turn up again, 1 for a fixed point
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The index of the earliest state identical to state `n`, which is `n` itself for states
+    /// before the cycle
+    #[allow(dead_code)]
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Remembers where each key was first seen, for spotting the first time a state comes round again
+///
+/// Keys can be the whole state, or just the part of it that matters, like a program counter.
+#[derive(Debug, Clone)]
+pub struct Detector<K> {
+    first_seen: HashMap<K, usize>,
+    observed: usize,
+}
+
+impl<K: Hash + Eq> Default for Detector<K> {
+    fn default() -> Detector<K> {
+        Detector::new()
+    }
+}
+
+impl<K: Hash + Eq> Detector<K> {
+    pub fn new() -> Detector<K> {
+        Detector {
+            first_seen: HashMap::new(),
+            observed: 0,
+        }
+    }
+
+    /// Note the key of the next state, returning the cycle it closes if it's been seen before
+    pub fn observe(&mut self, key: K) -> Option<Cycle> {
+        if let Some(start) = self.first_seen.get(&key) {
+            return Some(Cycle {
+                start: *start,
+                period: self.observed - start,
+            });
+        }
+        self.first_seen.insert(key, self.observed);
+        self.observed += 1;
+        None
+    }
+
+    /// Which state the key was first seen on, counting from 0
+    #[allow(dead_code)]
+    pub fn first_seen(&self, key: &K) -> Option<usize> {
+        self.first_seen.get(key).cloned()
+    }
+
+    /// How many distinct states have been observed
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.observed
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.observed == 0
+    }
+}
+
+/// Step from `start` until a state repeats, returning the cycle along with every state up to the
+/// repeat, which between them give any later state without stepping any further
+#[allow(dead_code)]
+pub fn find_cycle<S, F>(start: S, mut step: F) -> (Cycle, Vec<S>)
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut detector = Detector::new();
+    let mut history = vec![];
+    let mut state = start;
+    loop {
+        if let Some(cycle) = detector.observe(state.clone()) {
+            return (cycle, history);
+        }
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+}
+
+/// The state after stepping `n` times from `start`, skipping round the cycle if one turns up
+/// first, so `n` can be far larger than could ever be stepped through
+#[allow(dead_code)]
+pub fn state_at<S, F>(start: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut detector = Detector::new();
+    let mut history = vec![];
+    let mut state = start;
+    while history.len() < n {
+        if let Some(cycle) = detector.observe(state.clone()) {
+            return history.swap_remove(cycle.equivalent(n));
+        }
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+    state
+}
+
+/// Floyd's tortoise and hare, which finds the cycle while only ever holding two states, at the
+/// cost of stepping about three times as often as remembering every state would
+#[allow(dead_code)]
+pub fn floyd<S, F>(start: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // The hare moves twice as fast, so they meet somewhere a whole number of periods in
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // Restarting the tortoise and moving both at the same speed, they meet at the cycle's start
+    let mut start_index = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start_index += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    Cycle {
+        start: start_index,
+        period,
+    }
+}
+
+/// Brent's algorithm, which like `floyd` only holds two states but finds the period in fewer steps
+#[allow(dead_code)]
+pub fn brent<S, F>(start: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // The tortoise jumps to the hare at every power of two, until the hare runs into it
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // With the hare a period ahead, they meet at the cycle's start
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start_index = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start_index += 1;
+    }
+
+    Cycle {
+        start: start_index,
+        period,
+    }
+}
+
+/// Squaring then adding 3 modulo 29, which from 1 runs 1, 4, 19, then round 16, 27, 7, 23, 10
+#[cfg(test)]
+fn step(n: &usize) -> usize {
+    (n * n + 3) % 29
+}
+
+#[test]
+fn test_find_cycle() {
+    let (cycle, history) = find_cycle(1, step);
+    assert_eq!(
+        Cycle {
+            start: 3,
+            period: 5
+        },
+        cycle
+    );
+    assert_eq!(vec![1, 4, 19, 16, 27, 7, 23, 10], history);
+    assert_eq!(2, cycle.equivalent(2));
+    assert_eq!(6, cycle.equivalent(1_000_001));
+}
+
+#[test]
+fn test_all_methods_agree() {
+    for start in 0..29 {
+        let (cycle, _) = find_cycle(start, step);
+        assert_eq!(cycle, floyd(start, step), "floyd from {}", start);
+        assert_eq!(cycle, brent(start, step), "brent from {}", start);
+    }
+}
+
+#[test]
+fn test_state_at() {
+    for n in 0..30 {
+        let stepped = (0..n).fold(1, |s, _| step(&s));
+        assert_eq!(stepped, state_at(1, step, n));
+    }
+    assert_eq!(23, state_at(1, step, 1_000_000_000_001));
+}
+
+#[test]
+fn test_detector() {
+    let mut detector = Detector::new();
+    assert_eq!(None, detector.observe('a'));
+    assert_eq!(None, detector.observe('b'));
+    assert_eq!(None, detector.observe('c'));
+    assert_eq!(
+        Some(Cycle {
+            start: 1,
+            period: 2
+        }),
+        detector.observe('b')
+    );
+    assert_eq!(Some(2), detector.first_seen(&'c'));
+    assert_eq!(3, detector.len());
+}
@@ -0,0 +1,286 @@
+use runner::{self, Params, Solution};
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process;
+
+/// Example fixtures live in `resources/examples/YYYY/dayN/`, alongside an `answers.txt` manifest
+/// listing which input file produces which answer for which part:
+///
+/// ```text
+/// # input     part answer
+/// example.txt 1    436
+/// example.txt 2    175594
+/// ```
+///
+/// Any fields after the answer are `name=value` parameters for the solution, for examples that are
+/// run differently from the real puzzle, such as `preamble=5` for 2020 day 9.
+///
+/// Example files are read the same way as the day's real puzzle input, so blank line handling
+/// matches what the solution sees when run for real.
+const EXAMPLES_DIR: &str = "resources/examples";
+const MANIFEST: &str = "answers.txt";
+
+#[derive(Debug, PartialEq)]
+struct Expectation {
+    input: String,
+    part: u8,
+    answer: String,
+    params: Params,
+}
+
+fn parse_manifest(manifest: &str) -> Vec<Expectation> {
+    manifest
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            let fields: Vec<&str> = l.split_whitespace().collect();
+            assert!(fields.len() >= 3, "Malformed manifest line: {}", l);
+            let mut params = Params::default();
+            fields[3..].iter().for_each(|setting| params.set(setting));
+            Expectation {
+                input: fields[0].to_string(),
+                part: fields[1].parse().unwrap(),
+                answer: fields[2].to_string(),
+                params,
+            }
+        })
+        .collect()
+}
+
+/// An example whose answer didn't match, or which panicked trying to produce one
+#[derive(Debug)]
+pub struct Failure {
+    year: i32,
+    day: u32,
+    part: u8,
+    input: String,
+    expected: String,
+    actual: Result<String, String>,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} day {} part {} ({})",
+            self.year, self.day, self.part, self.input
+        )?;
+        match &self.actual {
+            Ok(actual) => {
+                // Point at the first character that differs so long answers are easy to compare
+                let first_difference = self
+                    .expected
+                    .chars()
+                    .zip(actual.chars())
+                    .take_while(|(e, a)| e == a)
+                    .count();
+                writeln!(f, "  expected: {}", self.expected)?;
+                writeln!(f, "    actual: {}", actual)?;
+                writeln!(f, "            {}^", " ".repeat(first_difference))
+            }
+            Err(panic) => {
+                writeln!(f, "  expected: {}", self.expected)?;
+                writeln!(f, "  panicked: {}", panic)
+            }
+        }
+    }
+}
+
+fn run_part(
+    solution: &Solution,
+    part: u8,
+    input: &[String],
+    params: &Params,
+) -> Result<String, String> {
+    let (_, solve) = solution.parts()[part as usize - 1];
+    panic::catch_unwind(AssertUnwindSafe(|| solve(input, params))).map_err(|e| {
+        e.downcast_ref::<String>()
+            .cloned()
+            .or_else(|| e.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+/// Run every example for a registered solution, returning how many were checked and which failed
+fn check_solution(solution: &Solution) -> (usize, Vec<Failure>) {
+    let dir = format!("{}/{}/day{}", EXAMPLES_DIR, solution.year, solution.day);
+    let manifest = match fs::read_to_string(Path::new(&dir).join(MANIFEST)) {
+        Ok(manifest) => manifest,
+        Err(_) => return (0, vec![]),
+    };
+
+    let expectations = parse_manifest(&manifest);
+    let failures = expectations
+        .iter()
+        .filter_map(|e| {
+            let path = format!("{}/{}", dir, e.input);
+            let input = solution.input.load_file(&path);
+            let actual = run_part(solution, e.part, &input, &e.params);
+            if actual.as_ref() == Ok(&e.answer) {
+                None
+            } else {
+                Some(Failure {
+                    year: solution.year,
+                    day: solution.day,
+                    part: e.part,
+                    input: path,
+                    expected: e.answer.clone(),
+                    actual,
+                })
+            }
+        })
+        .collect();
+
+    (expectations.len(), failures)
+}
+
+/// Fixture directories that don't line up with a registered solution are a mistake worth shouting
+/// about rather than silently skipping
+fn unregistered_fixtures(year: i32, solutions: &[Solution]) -> Vec<String> {
+    let dir = format!("{}/{}", EXAMPLES_DIR, year);
+    match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| !solutions.iter().any(|s| name == &format!("day{}", s.day)))
+            .map(|name| format!("{}/{}", dir, name))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+pub fn check(year: Option<i32>, day: Option<u32>) -> (usize, Vec<Failure>) {
+    let solutions = runner::select(year, day);
+    let mut checked = 0;
+    let mut failures = vec![];
+
+    for solution in &solutions {
+        let (c, f) = check_solution(solution);
+        checked += c;
+        failures.extend(f);
+    }
+
+    (checked, failures)
+}
+
+/// Check the examples for an optional year and day, exiting non-zero if any fail
+pub fn examples(args: &[String]) {
+    let year = args.first().map(|a| a.parse().unwrap());
+    let day = args.get(1).map(|a| a.parse().unwrap());
+
+    let years: Vec<i32> = year.map_or((2017..=2023).collect(), |y| vec![y]);
+    for y in years {
+        for dir in unregistered_fixtures(y, &runner::solutions_for_year(y)) {
+            println!("No registered solution for examples in {}", dir);
+        }
+    }
+
+    let (checked, failures) = check(year, day);
+    failures.iter().for_each(|f| println!("{}", f));
+    println!("{} examples checked, {} failed", checked, failures.len());
+
+    if !failures.is_empty() {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+fn assert_examples_pass(year: i32) {
+    let unregistered = unregistered_fixtures(year, &runner::solutions_for_year(year));
+    assert!(
+        unregistered.is_empty(),
+        "Examples without a registered solution: {:?}",
+        unregistered
+    );
+
+    let (checked, failures) = check(Some(year), None);
+    assert!(checked > 0, "No examples found for {}", year);
+    assert!(
+        failures.is_empty(),
+        "{} of {} examples failed:\n\n{}",
+        failures.len(),
+        checked,
+        failures
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+}
+
+#[test]
+fn test_parse_manifest() {
+    let manifest = "# input part answer\n\nexample.txt 1 436\n  example2.txt 2 CABDFE size=5\n";
+    let mut params = Params::default();
+    params.set("size=5");
+    assert_eq!(
+        vec![
+            Expectation {
+                input: "example.txt".to_string(),
+                part: 1,
+                answer: "436".to_string(),
+                params: Params::default(),
+            },
+            Expectation {
+                input: "example2.txt".to_string(),
+                part: 2,
+                answer: "CABDFE".to_string(),
+                params,
+            },
+        ],
+        parse_manifest(manifest)
+    );
+}
+
+#[test]
+fn test_failure_display() {
+    let failure = Failure {
+        year: 2020,
+        day: 15,
+        part: 1,
+        input: "example.txt".to_string(),
+        expected: "436".to_string(),
+        actual: Ok("446".to_string()),
+    };
+    assert_eq!(
+        "2020 day 15 part 1 (example.txt)\n  expected: 436\n    actual: 446\n             ^\n",
+        failure.to_string()
+    );
+}
+
+#[test]
+fn examples_2017() {
+    assert_examples_pass(2017);
+}
+
+#[test]
+fn examples_2018() {
+    assert_examples_pass(2018);
+}
+
+#[test]
+fn examples_2019() {
+    assert_examples_pass(2019);
+}
+
+#[test]
+fn examples_2020() {
+    assert_examples_pass(2020);
+}
+
+#[test]
+fn examples_2021() {
+    assert_examples_pass(2021);
+}
+
+#[test]
+fn examples_2022() {
+    assert_examples_pass(2022);
+}
+
+#[test]
+fn examples_2023() {
+    assert_examples_pass(2023);
+}
@@ -0,0 +1,203 @@
+use rng::Rng;
+use runner::{self, Params, Report, Solution};
+
+const DEFAULT_SIZE: usize = 100;
+const DEFAULT_SEED: u64 = 2020;
+
+/// Which generated input to use in place of a day's real puzzle input
+///
+/// What `size` means is up to each day's generator (lines, records, grid width, ...), but bigger
+/// always means more work for the solution, so it can be used to see how a solution scales.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Generated {
+    pub size: usize,
+    pub seed: u64,
+}
+
+impl Default for Generated {
+    fn default() -> Generated {
+        Generated {
+            size: DEFAULT_SIZE,
+            seed: DEFAULT_SEED,
+        }
+    }
+}
+
+impl Generated {
+    /// The generated input as it would be written to a puzzle input file
+    pub fn text(&self, solution: &Solution) -> Vec<String> {
+        (solution.generate)(&mut Rng::new(self.seed), self.size)
+    }
+
+    /// The generated input as the solution would see it if it were read from a file
+    pub fn lines(&self, solution: &Solution) -> Vec<String> {
+        solution.input.tidy(self.text(solution))
+    }
+
+    /// Short description of the input, recorded alongside timings so they're only ever compared
+    /// against timings for the same input
+    pub fn label(&self) -> String {
+        format!("gen:{}:{}", self.size, self.seed)
+    }
+}
+
+/// Command line options for `generate`
+///
+/// ```text
+/// generate year [day] [--size N] [--seed S]
+///          [--solve [--alloc] [--json] [--param name=value]...]
+/// ```
+#[derive(Debug, PartialEq)]
+struct Options {
+    year: i32,
+    day: Option<u32>,
+    generated: Generated,
+    solve: bool,
+    params: Params,
+    report: Report,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Options {
+        let mut year = None;
+        let mut day = None;
+        let mut generated = Generated::default();
+        let mut solve = false;
+        let mut params = Params::default();
+        let mut report = Report::default();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .unwrap_or_else(|| panic!("Missing value for {}", arg))
+                    .to_string()
+            };
+            match arg.as_str() {
+                "--size" => generated.size = value().parse().unwrap(),
+                "--seed" => generated.seed = value().parse().unwrap(),
+                "--solve" => solve = true,
+                "--param" => params.set(&value()),
+                "--alloc" => report.allocations = true,
+                "--json" => report.json = true,
+                positional if year.is_none() => year = Some(positional.parse().unwrap()),
+                positional if day.is_none() => day = Some(positional.parse().unwrap()),
+                unknown => panic!("Unexpected argument: {}", unknown),
+            }
+        }
+
+        Options {
+            year: year.expect("Need a year to generate input for"),
+            day,
+            generated,
+            solve,
+            params,
+            report,
+        }
+    }
+}
+
+/// Print a generated input for a day, or with `--solve` run the selected days against their
+/// generated inputs instead of the real ones
+pub fn generate(args: &[String]) {
+    let options = Options::parse(args);
+    let solutions = runner::select(Some(options.year), options.day);
+    assert!(
+        !solutions.is_empty(),
+        "No solution registered for {} day {:?}",
+        options.year,
+        options.day
+    );
+
+    if options.solve {
+        if !options.report.json {
+            println!("Input: {}", options.generated.label());
+        }
+        runner::print_solutions(
+            options.year,
+            &solutions,
+            Some(options.generated),
+            &options.params,
+            options.report,
+        );
+    } else {
+        assert!(
+            solutions.len() == 1,
+            "Pick a single day to print generated input for"
+        );
+        options
+            .generated
+            .text(&solutions[0])
+            .iter()
+            .for_each(|l| println!("{}", l));
+    }
+}
+
+#[test]
+fn test_parse_options() {
+    let args: Vec<String> = [
+        "2020",
+        "9",
+        "--size",
+        "50",
+        "--solve",
+        "--param",
+        "preamble=5",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    let mut params = Params::default();
+    params.set("preamble=5");
+    assert_eq!(
+        Options {
+            year: 2020,
+            day: Some(9),
+            generated: Generated {
+                size: 50,
+                seed: DEFAULT_SEED,
+            },
+            solve: true,
+            params,
+            report: Report::default(),
+        },
+        Options::parse(&args)
+    );
+}
+
+#[test]
+fn test_generated_inputs_are_reproducible() {
+    for solution in runner::all_solutions() {
+        let generated = Generated { size: 8, seed: 7 };
+        assert_eq!(
+            generated.lines(&solution),
+            generated.lines(&solution),
+            "{} day {} generated different input for the same seed",
+            solution.year,
+            solution.day
+        );
+    }
+}
+
+#[test]
+fn test_generated_inputs_can_be_solved() {
+    for solution in runner::all_solutions() {
+        for seed in 0..5 {
+            let generated = Generated { size: 12, seed };
+            let input = generated.lines(&solution);
+            for (part, solve) in solution.parts().iter() {
+                let answer = std::panic::catch_unwind(|| solve(&input, &Params::default()));
+                assert!(
+                    answer.is_ok(),
+                    "{} day {} part {} panicked on {} input:\n{}",
+                    solution.year,
+                    solution.day,
+                    part,
+                    generated.label(),
+                    input.join("\n")
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use point::{Point2, Vec2};
+
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn cross(a: Vec2, b: Vec2) -> isize {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Vec2, b: Vec2) -> isize {
+    a.x * b.x + a.y * b.y
+}
+
+/// A straight line between two points on the integer grid, including both ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point2,
+    pub end: Point2,
+}
+
+impl Segment {
+    pub fn new(start: Point2, end: Point2) -> Segment {
+        Segment { start, end }
+    }
+
+    #[allow(dead_code)]
+    pub fn is_horizontal_or_vertical(&self) -> bool {
+        self.start.x == self.end.x || self.start.y == self.end.y
+    }
+
+    /// The smallest whole step along the segment that lands exactly on another grid point, and how
+    /// many of those steps it takes to get from one end to the other
+    fn lattice_step(&self) -> (Vec2, isize) {
+        let d = self.end - self.start;
+        match gcd(d.x, d.y) {
+            0 => (Vec2::ZERO, 0),
+            steps => (Vec2::new(d.x / steps, d.y / steps), steps),
+        }
+    }
+
+    /// Every grid point the segment passes exactly through, from start to end
+    ///
+    /// For horizontal, vertical and 45° segments that's every cell along the way, for other slopes
+    /// it skips the cells the line only clips.
+    #[allow(dead_code)]
+    pub fn lattice_points(&self) -> Vec<Point2> {
+        let (step, steps) = self.lattice_step();
+        (0..=steps).map(|k| self.start + step * k).collect()
+    }
+
+    /// The cells a line drawn from start to end would colour in, one per step along the longer
+    /// axis, using Bresenham's algorithm so any slope can be drawn with integers alone
+    #[allow(dead_code)]
+    pub fn rasterise(&self) -> Vec<Point2> {
+        let d = self.end - self.start;
+        let (sx, sy) = (d.x.signum(), d.y.signum());
+        let (ax, ay) = (d.x.abs(), -d.y.abs());
+
+        let mut points = vec![self.start];
+        let mut current = self.start;
+        let mut error = ax + ay;
+        while current != self.end {
+            let doubled = 2 * error;
+            if doubled >= ay {
+                error += ay;
+                current.x += sx;
+            }
+            if doubled <= ax {
+                error += ax;
+                current.y += sy;
+            }
+            points.push(current);
+        }
+        points
+    }
+
+    /// Whether `p` lies on the segment, ends included
+    pub fn contains(&self, p: Point2) -> bool {
+        cross(self.end - self.start, p - self.start) == 0
+            && p.x >= self.start.x.min(self.end.x)
+            && p.x <= self.start.x.max(self.end.x)
+            && p.y >= self.start.y.min(self.end.y)
+            && p.y <= self.start.y.max(self.end.y)
+    }
+
+    /// Every grid point that lies on both segments, worked out from the segments' equations rather
+    /// than by walking them
+    ///
+    /// Crossing segments share at most one point, which only counts if it lands exactly on the
+    /// grid. Segments lying along the same line can share a whole run of points.
+    pub fn lattice_intersections(&self, other: &Segment) -> Vec<Point2> {
+        let d1 = self.end - self.start;
+        let d2 = other.end - other.start;
+        let between = other.start - self.start;
+
+        if d1 == Vec2::ZERO {
+            return if other.contains(self.start) {
+                vec![self.start]
+            } else {
+                vec![]
+            };
+        }
+
+        let denominator = cross(d1, d2);
+        if denominator == 0 {
+            if cross(d1, between) != 0 {
+                // Parallel but on different lines
+                return vec![];
+            }
+
+            // On the same line, so every grid point on the other segment is a whole number of this
+            // segment's steps along, and it's just a matter of finding which steps they cover
+            let (step, steps) = self.lattice_step();
+            let along = |p: Point2| dot(p - self.start, step) / dot(step, step);
+            let (a, b) = (along(other.start), along(other.end));
+            let (first, last) = (a.min(b).max(0), a.max(b).min(steps));
+            return (first..=last).map(|k| self.start + step * k).collect();
+        }
+
+        // Solve start + t * d1 == other.start + u * d2 for t and u, both of which have to be within
+        // 0..=1 for the point to be on both segments
+        let (t, u) = (cross(between, d2), cross(between, d1));
+        let in_range = |n: isize| {
+            if denominator > 0 {
+                0 <= n && n <= denominator
+            } else {
+                denominator <= n && n <= 0
+            }
+        };
+        if !in_range(t) || !in_range(u) {
+            return vec![];
+        }
+
+        let (x, y) = (d1.x * t, d1.y * t);
+        if x % denominator != 0 || y % denominator != 0 {
+            // They cross between grid points
+            return vec![];
+        }
+        vec![self.start + Vec2::new(x / denominator, y / denominator)]
+    }
+
+    /// How many steps it takes to get from the start to `p`, which is somewhere on the segment,
+    /// moving diagonally where the segment does
+    pub fn steps_to(&self, p: Point2) -> isize {
+        self.start.chebyshev(p)
+    }
+}
+
+/// A segment of a polyline, along with how far along the polyline it starts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Leg {
+    pub segment: Segment,
+    pub steps_before: isize,
+}
+
+impl Leg {
+    /// How many steps along the whole polyline it takes to get to `p`, which is on this leg
+    pub fn steps_to(&self, p: Point2) -> isize {
+        self.steps_before + self.segment.steps_to(p)
+    }
+}
+
+/// A path made of straight segments joined end to end, like a wire laid out by a list of moves
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polyline {
+    legs: Vec<Leg>,
+}
+
+impl Polyline {
+    /// Follow the moves one after another from the start point
+    pub fn from_moves<I>(start: Point2, moves: I) -> Polyline
+    where
+        I: IntoIterator<Item = Vec2>,
+    {
+        let mut legs = vec![];
+        let mut position = start;
+        let mut steps_before = 0;
+        for m in moves {
+            let segment = Segment::new(position, position + m);
+            legs.push(Leg {
+                segment,
+                steps_before,
+            });
+            steps_before += segment.steps_to(segment.end);
+            position = segment.end;
+        }
+        Polyline { legs }
+    }
+
+    #[allow(dead_code)]
+    pub fn legs(&self) -> &[Leg] {
+        &self.legs
+    }
+
+    /// Every grid point where the two polylines meet, with the steps each took to get there
+    ///
+    /// Where a polyline passes through a point more than once each visit is listed separately, and
+    /// the corners where one leg joins the next turn up for both legs.
+    pub fn intersections(&self, other: &Polyline) -> Vec<(Point2, isize, isize)> {
+        let mut found = vec![];
+        for a in &self.legs {
+            for b in &other.legs {
+                for p in a.segment.lattice_intersections(&b.segment) {
+                    found.push((p, a.steps_to(p), b.steps_to(p)));
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+fn segment(x1: isize, y1: isize, x2: isize, y2: isize) -> Segment {
+    Segment::new(Point2::new(x1, y1), Point2::new(x2, y2))
+}
+
+#[cfg(test)]
+fn points(points: &[(isize, isize)]) -> Vec<Point2> {
+    points.iter().map(|(x, y)| Point2::new(*x, *y)).collect()
+}
+
+#[test]
+fn test_lattice_points() {
+    assert_eq!(
+        points(&[(9, 7), (8, 8), (7, 9)]),
+        segment(9, 7, 7, 9).lattice_points()
+    );
+    assert_eq!(
+        points(&[(0, 0), (2, 1), (4, 2)]),
+        segment(0, 0, 4, 2).lattice_points()
+    );
+    assert_eq!(points(&[(3, 3)]), segment(3, 3, 3, 3).lattice_points());
+}
+
+#[test]
+fn test_rasterise() {
+    assert_eq!(
+        points(&[(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]),
+        segment(0, 0, 4, 2).rasterise()
+    );
+    assert_eq!(
+        points(&[(2, 5), (2, 4), (2, 3)]),
+        segment(2, 5, 2, 3).rasterise()
+    );
+
+    // At the slopes lattice points cover every cell, both agree
+    for s in &[
+        segment(0, 9, 5, 9),
+        segment(8, 0, 0, 8),
+        segment(7, 0, 7, 4),
+        segment(5, 5, 8, 2),
+    ] {
+        assert_eq!(s.lattice_points(), s.rasterise());
+    }
+}
+
+#[test]
+fn test_crossing_intersections() {
+    assert_eq!(
+        points(&[(3, 3)]),
+        segment(0, 3, 6, 3).lattice_intersections(&segment(3, 0, 3, 9))
+    );
+    assert_eq!(
+        points(&[(2, 2)]),
+        segment(0, 0, 4, 4).lattice_intersections(&segment(0, 4, 4, 0))
+    );
+    // Touching at the very end still counts
+    assert_eq!(
+        points(&[(6, 3)]),
+        segment(0, 3, 6, 3).lattice_intersections(&segment(6, 3, 6, 9))
+    );
+    // Lines that would cross if they were longer
+    assert!(segment(0, 3, 2, 3)
+        .lattice_intersections(&segment(3, 0, 3, 9))
+        .is_empty());
+    // Crossing between grid points
+    assert!(segment(0, 0, 1, 1)
+        .lattice_intersections(&segment(0, 1, 1, 0))
+        .is_empty());
+}
+
+#[test]
+fn test_collinear_intersections() {
+    assert_eq!(
+        points(&[(0, 9), (1, 9), (2, 9)]),
+        segment(0, 9, 5, 9).lattice_intersections(&segment(2, 9, -4, 9))
+    );
+    assert_eq!(
+        points(&[(4, 2)]),
+        segment(0, 0, 4, 2).lattice_intersections(&segment(4, 2, 8, 4))
+    );
+    assert!(segment(0, 0, 4, 2)
+        .lattice_intersections(&segment(0, 1, 4, 3))
+        .is_empty());
+    assert!(segment(0, 0, 2, 0)
+        .lattice_intersections(&segment(3, 0, 5, 0))
+        .is_empty());
+}
+
+#[test]
+fn test_polyline_intersections() {
+    let moves = |m: &[(isize, isize)]| m.iter().map(|(x, y)| Vec2::new(*x, *y)).collect::<Vec<_>>();
+    // R8,U5,L5,D3 and U7,R6,D4,L4 from 2019 day 3, with up being negative y
+    let wire1 = Polyline::from_moves(Point2::ORIGIN, moves(&[(8, 0), (0, -5), (-5, 0), (0, 3)]));
+    let wire2 = Polyline::from_moves(Point2::ORIGIN, moves(&[(0, -7), (6, 0), (0, 4), (-4, 0)]));
+
+    let mut crossings = wire1.intersections(&wire2);
+    crossings.retain(|(p, _, _)| *p != Point2::ORIGIN);
+    crossings.sort();
+    assert_eq!(
+        vec![(Point2::new(3, -3), 20, 20), (Point2::new(6, -5), 15, 15)],
+        crossings
+    );
+}
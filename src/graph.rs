@@ -0,0 +1,357 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+pub type NodeId = usize;
+
+/// A directed graph whose nodes are named by strings, with a weight on every edge
+///
+/// Names are interned as they're first seen, so everything past building the graph works on plain
+/// `NodeId` indices rather than hashing strings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph<W = ()> {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<W> Default for Graph<W> {
+    fn default() -> Graph<W> {
+        Graph::new()
+    }
+}
+
+impl<W> Graph<W> {
+    pub fn new() -> Graph<W> {
+        Graph {
+            names: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    /// The id of the named node, adding it if it's not in the graph yet
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).cloned()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Add an edge between the named nodes, adding the nodes too if need be
+    pub fn add_edge(&mut self, from: &str, to: &str, weight: W) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.edges[from].push((to, weight));
+    }
+
+    /// The nodes `id` has edges to, along with the edges' weights
+    #[allow(dead_code)]
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.edges[id]
+    }
+
+    /// Every node reachable from `start`, including itself, nearest first
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = vec![];
+        let mut queue = VecDeque::from(vec![start]);
+        seen[start] = true;
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for (next, _) in &self.edges[id] {
+                if !seen[*next] {
+                    seen[*next] = true;
+                    queue.push_back(*next);
+                }
+            }
+        }
+        order
+    }
+
+    /// Every node reachable from `start`, including itself, following each path as deep as it goes
+    /// before backing up
+    #[allow(dead_code)]
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if seen[id] {
+                continue;
+            }
+            seen[id] = true;
+            order.push(id);
+            // Pushed in reverse so the first edge is followed first
+            stack.extend(self.edges[id].iter().rev().map(|(next, _)| *next));
+        }
+        order
+    }
+
+    /// Work out a value for `start` from the values of the nodes it has edges to, and theirs from
+    /// the nodes they have edges to, and so on, working each node out only once however many paths
+    /// lead to it
+    ///
+    /// `combine` is given a node and each of its edges' weights alongside the value already worked
+    /// out for the far end. Panics if there's a cycle reachable from `start`, as there'd be no
+    /// bottom to work up from.
+    pub fn aggregate<T, F>(&self, start: NodeId, mut combine: F) -> T
+    where
+        T: Clone,
+        F: FnMut(NodeId, &[(&W, T)]) -> T,
+    {
+        let mut memo: Vec<Option<T>> = vec![None; self.len()];
+        let mut visiting = vec![false; self.len()];
+        self.aggregate_from(start, &mut combine, &mut memo, &mut visiting)
+    }
+
+    fn aggregate_from<T, F>(
+        &self,
+        id: NodeId,
+        combine: &mut F,
+        memo: &mut Vec<Option<T>>,
+        visiting: &mut Vec<bool>,
+    ) -> T
+    where
+        T: Clone,
+        F: FnMut(NodeId, &[(&W, T)]) -> T,
+    {
+        if let Some(value) = &memo[id] {
+            return value.clone();
+        }
+        assert!(!visiting[id], "Cycle through {}", self.names[id]);
+        visiting[id] = true;
+
+        let children: Vec<(&W, T)> = self.edges[id]
+            .iter()
+            .map(|(next, weight)| (weight, self.aggregate_from(*next, combine, memo, visiting)))
+            .collect();
+        let value = combine(id, &children);
+
+        visiting[id] = false;
+        memo[id] = Some(value.clone());
+        value
+    }
+
+    /// A path of nodes that leads back round to its own start, if the graph has any
+    #[allow(dead_code)]
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        // Nodes are unvisited, on the current path, or done with and known not to be on a cycle
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            Unvisited,
+            OnPath,
+            Done,
+        }
+
+        let mut state = vec![State::Unvisited; self.len()];
+        for root in 0..self.len() {
+            if state[root] != State::Unvisited {
+                continue;
+            }
+
+            // Each stack entry is a node on the current path and how many of its edges are done
+            let mut path: Vec<(NodeId, usize)> = vec![(root, 0)];
+            state[root] = State::OnPath;
+            while let Some((id, edge)) = path.last_mut() {
+                match self.edges[*id].get(*edge) {
+                    None => {
+                        state[*id] = State::Done;
+                        path.pop();
+                    }
+                    Some((next, _)) => {
+                        *edge += 1;
+                        match state[*next] {
+                            State::Unvisited => {
+                                state[*next] = State::OnPath;
+                                path.push((*next, 0));
+                            }
+                            State::OnPath => {
+                                let from = path.iter().position(|(p, _)| p == next).unwrap();
+                                return Some(path[from..].iter().map(|(p, _)| *p).collect());
+                            }
+                            State::Done => {}
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Every node ordered so that each comes before all the nodes it has edges to, or `None` if a
+    /// cycle makes that impossible
+    ///
+    /// Uses Kahn's algorithm, and whenever more than one node is ready to go next the one with the
+    /// smallest `key` goes first.
+    pub fn topological_sort_by_key<K, F>(&self, mut key: F) -> Option<Vec<NodeId>>
+    where
+        K: Ord,
+        F: FnMut(NodeId) -> K,
+    {
+        let mut incoming = vec![0; self.len()];
+        self.edges
+            .iter()
+            .flatten()
+            .for_each(|(to, _)| incoming[*to] += 1);
+
+        let mut ready: BinaryHeap<Reverse<(K, NodeId)>> = (0..self.len())
+            .filter(|id| incoming[*id] == 0)
+            .map(|id| Reverse((key(id), id)))
+            .collect();
+
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(Reverse((_, id))) = ready.pop() {
+            order.push(id);
+            for (next, _) in &self.edges[id] {
+                incoming[*next] -= 1;
+                if incoming[*next] == 0 {
+                    ready.push(Reverse((key(*next), *next)));
+                }
+            }
+        }
+
+        // Anything left over was waiting on itself somewhere along the way
+        match order.len() == self.len() {
+            true => Some(order),
+            false => None,
+        }
+    }
+}
+
+impl<W: Clone> Graph<W> {
+    /// The same graph with every edge pointing the other way
+    pub fn reversed(&self) -> Graph<W> {
+        let mut edges = vec![vec![]; self.len()];
+        for (from, out) in self.edges.iter().enumerate() {
+            for (to, weight) in out {
+                edges[*to].push((from, weight.clone()));
+            }
+        }
+        Graph {
+            names: self.names.clone(),
+            ids: self.ids.clone(),
+            edges,
+        }
+    }
+}
+
+#[cfg(test)]
+fn graph(edges: &[(&str, &str)]) -> Graph {
+    let mut graph = Graph::new();
+    edges
+        .iter()
+        .for_each(|(from, to)| graph.add_edge(from, to, ()));
+    graph
+}
+
+#[cfg(test)]
+fn names(graph: &Graph, ids: &[NodeId]) -> String {
+    ids.iter().map(|id| graph.name(*id)).collect()
+}
+
+#[test]
+fn test_interning() {
+    let mut graph = graph(&[("a", "b"), ("b", "c")]);
+    assert_eq!(3, graph.len());
+    assert_eq!(Some(1), graph.id("b"));
+    assert_eq!(1, graph.node("b"));
+    assert_eq!(3, graph.node("d"));
+    assert_eq!(None, graph.id("e"));
+}
+
+#[test]
+fn test_traversal() {
+    let graph = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "e"), ("e", "a")]);
+    let a = graph.id("a").unwrap();
+    assert_eq!("abcde", names(&graph, &graph.bfs(a)));
+    assert_eq!("abdce", names(&graph, &graph.dfs(a)));
+    assert_eq!("d", names(&graph, &graph.dfs(graph.id("d").unwrap())));
+
+    let reversed = graph.reversed();
+    assert_eq!(
+        "dbaec",
+        names(&reversed, &reversed.bfs(graph.id("d").unwrap()))
+    );
+}
+
+#[test]
+fn test_aggregate() {
+    // Each bag holds the weighted count of bags in it, plus those bags' own contents
+    let mut bags = Graph::new();
+    bags.add_edge("gold", "red", 2);
+    bags.add_edge("gold", "blue", 3);
+    bags.add_edge("red", "blue", 4);
+    bags.add_edge("blue", "green", 1);
+    let inside = bags.aggregate(bags.id("gold").unwrap(), |_, children| {
+        children
+            .iter()
+            .map(|(count, inner)| *count * (1 + inner))
+            .sum::<usize>()
+    });
+    assert_eq!(2 * (1 + 4 * 2) + 3 * 2, inside);
+}
+
+#[test]
+#[should_panic(expected = "Cycle")]
+fn test_aggregate_cycle() {
+    let graph = graph(&[("a", "b"), ("b", "a")]);
+    graph.aggregate(0, |_, children| children.len());
+}
+
+#[test]
+fn test_find_cycle() {
+    assert_eq!(
+        None,
+        graph(&[("a", "b"), ("a", "c"), ("b", "c")]).find_cycle()
+    );
+
+    let cyclic = graph(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "b")]);
+    assert_eq!(
+        Some("bcd".to_string()),
+        cyclic.find_cycle().map(|c| names(&cyclic, &c))
+    );
+}
+
+#[test]
+fn test_topological_sort() {
+    // 2018 day 7's example, where the alphabetically first ready step goes next
+    let steps = graph(&[
+        ("C", "A"),
+        ("C", "F"),
+        ("A", "B"),
+        ("A", "D"),
+        ("B", "E"),
+        ("D", "E"),
+        ("F", "E"),
+    ]);
+    let order = steps.topological_sort_by_key(|id| steps.name(id).to_string());
+    assert_eq!(Some("CABDFE".to_string()), order.map(|o| names(&steps, &o)));
+
+    let order = steps.topological_sort_by_key(|id| Reverse(steps.name(id).to_string()));
+    assert_eq!(Some("CFADBE".to_string()), order.map(|o| names(&steps, &o)));
+
+    assert_eq!(
+        None,
+        graph(&[("a", "b"), ("b", "a")]).topological_sort_by_key(|id| id)
+    );
+}
@@ -0,0 +1,331 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Offsets to the cells sharing an edge with a cell
+#[allow(dead_code)]
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to the cells sharing an edge or a corner with a cell
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular map of cells, stored row by row, with `(0, 0)` in the top left
+///
+/// Either axis can wrap around, so stepping off one edge comes back on at the opposite one, like
+/// the endlessly repeating slope in 2020 day 3.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    wrap_x: bool,
+    wrap_y: bool,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn<F>(width: usize, height: usize, mut cell: F) -> Grid<T>
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y))
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+            wrap_x: false,
+            wrap_y: false,
+        }
+    }
+
+    /// Parse a character map, one line per row, turning each character in to a cell
+    pub fn parse<F>(lines: &[String], mut cell: F) -> Grid<T>
+    where
+        F: FnMut(char) -> T,
+    {
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines {
+            let row_start = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            assert!(
+                cells.len() - row_start == width,
+                "Ragged grid, expected {} cells in row: {}",
+                width,
+                line
+            );
+        }
+
+        Grid {
+            width,
+            height: lines.len(),
+            cells,
+            wrap_x: false,
+            wrap_y: false,
+        }
+    }
+
+    /// Make stepping off either edge of the given axes come back on at the opposite edge
+    pub fn wrapping(mut self, wrap_x: bool, wrap_y: bool) -> Grid<T> {
+        self.wrap_x = wrap_x;
+        self.wrap_y = wrap_y;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn axis(position: isize, length: usize, wrap: bool) -> Option<usize> {
+        if wrap && length > 0 {
+            Some(position.rem_euclid(length as isize) as usize)
+        } else if position >= 0 && (position as usize) < length {
+            Some(position as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Where `(x, y)` lands on the grid once any wrapping is applied, if it lands on it at all
+    pub fn position(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        Some((
+            Grid::<T>::axis(x, self.width, self.wrap_x)?,
+            Grid::<T>::axis(y, self.height, self.wrap_y)?,
+        ))
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.position(x, y).map(|p| &self[p])
+    }
+
+    #[allow(dead_code)]
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        self.position(x, y).map(move |p| &mut self[p])
+    }
+
+    /// Every cell with its position, row by row
+    #[allow(dead_code)]
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// Every cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is off the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells at the given offsets from `(x, y)` that land on the grid
+    pub fn around<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let position = self.position(x as isize + dx, y as isize + dy)?;
+            Some((position, &self[position]))
+        })
+    }
+
+    /// The up to 4 cells sharing an edge with `(x, y)`
+    #[allow(dead_code)]
+    pub fn neighbours_4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.around(x, y, &NEIGHBOURS_4)
+    }
+
+    /// The up to 8 cells sharing an edge or a corner with `(x, y)`
+    pub fn neighbours_8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.around(x, y, &NEIGHBOURS_8)
+    }
+
+    /// The cells passed by repeatedly stepping from `(x, y)` by `(dx, dy)`, not including `(x, y)`
+    /// itself, until stepping off the grid or wrapping back round to the start
+    pub fn ray(
+        &self,
+        x: usize,
+        y: usize,
+        (dx, dy): (isize, isize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        assert!(dx != 0 || dy != 0, "A ray has to go somewhere");
+        let mut current = (x as isize, y as isize);
+        std::iter::from_fn(move || {
+            current = (current.0 + dx, current.1 + dy);
+            let position = self.position(current.0, current.1)?;
+            current = (position.0 as isize, position.1 as isize);
+            if position == (x, y) {
+                None
+            } else {
+                Some((position, &self[position]))
+            }
+        })
+    }
+
+    /// The first cell in the given direction from `(x, y)` matching the predicate
+    pub fn cast<P>(
+        &self,
+        x: usize,
+        y: usize,
+        direction: (isize, isize),
+        mut predicate: P,
+    ) -> Option<((usize, usize), &T)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.ray(x, y, direction).find(|(_, cell)| predicate(cell))
+    }
+
+    #[allow(dead_code)]
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            wrap_x: self.wrap_x,
+            wrap_y: self.wrap_y,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the grid",
+            x,
+            y
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the grid",
+            x,
+            y
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Renders the grid as it would appear in a puzzle, each cell's own rendering one after another
+/// and a line per row
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn example() -> Grid<char> {
+    Grid::parse(
+        &["#..".to_string(), ".#.".to_string(), "..#".to_string()],
+        |c| c,
+    )
+}
+
+#[test]
+fn test_parse_and_render() {
+    let grid = example();
+    assert_eq!(3, grid.width());
+    assert_eq!(3, grid.height());
+    assert_eq!('#', grid[(1, 1)]);
+    assert_eq!("#..\n.#.\n..#", grid.to_string());
+    assert_eq!(
+        vec![&'.', &'#', &'.'],
+        grid.column(1).collect::<Vec<&char>>()
+    );
+    assert_eq!(&['.', '.', '#'], grid.row(2));
+}
+
+#[test]
+#[should_panic(expected = "Ragged grid")]
+fn test_parse_ragged() {
+    Grid::parse(&["##".to_string(), "#".to_string()], |c| c);
+}
+
+#[test]
+fn test_neighbours() {
+    let grid = example();
+    assert_eq!(2, grid.neighbours_4(0, 0).count());
+    assert_eq!(3, grid.neighbours_8(0, 0).count());
+    assert_eq!(
+        2,
+        grid.neighbours_8(1, 1).filter(|(_, c)| **c == '#').count()
+    );
+
+    let wrapped = example().wrapping(true, true);
+    assert_eq!(8, wrapped.neighbours_8(0, 0).count());
+    assert_eq!(Some(&'#'), wrapped.get(-1, -1));
+    assert_eq!(None, example().get(-1, -1));
+}
+
+#[test]
+fn test_ray_and_cast() {
+    let grid = example();
+    assert_eq!(
+        vec![(1, 1), (2, 2)],
+        grid.ray(0, 0, (1, 1)).map(|(p, _)| p).collect::<Vec<_>>()
+    );
+    assert_eq!(Some(((2, 2), &'#')), grid.cast(1, 1, (1, 1), |c| *c == '#'));
+    assert_eq!(None, grid.cast(0, 0, (1, 0), |c| *c == '#'));
+
+    // Wrapping round stops at the start rather than going on forever
+    let wrapped = example().wrapping(true, false);
+    assert_eq!(2, wrapped.ray(0, 0, (1, 0)).count());
+    assert_eq!(
+        vec![(2, 1), (1, 2)],
+        wrapped
+            .ray(0, 0, (2, 1))
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>()
+    );
+}
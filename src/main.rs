@@ -5,9 +5,9 @@ extern crate nom;
 extern crate phf;
 extern crate regex;
 
-use chrono::{Datelike, Utc};
 use std::env;
 
+mod alloc;
 mod aoc2017;
 mod aoc2018;
 mod aoc2019;
@@ -15,22 +15,41 @@ mod aoc2020;
 mod aoc2021;
 mod aoc2022;
 mod aoc2023;
+mod automaton;
+mod bench;
+mod bits;
+mod counter;
+mod cycle;
+mod examples;
+mod generate;
+mod geometry;
+mod graph;
+mod grid;
+mod matrix;
+mod number_theory;
+mod parse;
+mod point;
+#[cfg(test)]
+mod property;
+mod range_set;
+mod rect;
+mod rng;
+mod runner;
+mod search;
+// Only drawing 2021 day 5's vents for its tests needs it so far
+#[cfg(test)]
+mod sparse_grid;
+mod target_sum;
 mod util;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    let year: i32 = args
-        .get(1)
-        .map_or(Utc::now().year(), |a| a.parse().unwrap());
 
-    match year {
-        2017 => aoc2017::aoc2017(),
-        2018 => aoc2018::aoc2018(),
-        2019 => aoc2019::aoc2019(),
-        2020 => aoc2020::aoc2020(),
-        2021 => aoc2021::aoc2021(),
-        2022 => aoc2022::aoc2022(),
-        2023 => aoc2023::aoc2023(),
-        _ => panic!("No advent of code solutions for {}", year),
+    match args.get(1).map(String::as_str) {
+        Some("bench") => bench::bench(&args[2..]),
+        Some("compare") => bench::compare(&args[2..]),
+        Some("examples") => examples::examples(&args[2..]),
+        Some("generate") => generate::generate(&args[2..]),
+        _ => runner::run(&args[1..]),
     }
 }
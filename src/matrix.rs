@@ -0,0 +1,148 @@
+use std::ops::{Index, IndexMut};
+
+/// A square matrix of non-negative integers, for stepping linear recurrences a huge number of
+/// times by raising their transition matrix to a power
+///
+/// Arithmetic is either exact, returning `None` if anything overflows a `u128`, or modulo some
+/// modulus of at most 2^64 so that products of two remainders always fit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    size: usize,
+    cells: Vec<u128>,
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = u128;
+
+    /// Indexed by `(row, column)`
+    fn index(&self, (row, column): (usize, usize)) -> &u128 {
+        &self.cells[row * self.size + column]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut u128 {
+        &mut self.cells[row * self.size + column]
+    }
+}
+
+/// Add `a * b` to `total`, either exactly or modulo `modulus`
+fn multiply_add(total: u128, a: u128, b: u128, modulus: Option<u128>) -> Option<u128> {
+    match modulus {
+        Some(m) => Some((total + (a % m) * (b % m) % m) % m),
+        None => total.checked_add(a.checked_mul(b)?),
+    }
+}
+
+impl Matrix {
+    pub fn zero(size: usize) -> Matrix {
+        Matrix {
+            size,
+            cells: vec![0; size * size],
+        }
+    }
+
+    pub fn identity(size: usize) -> Matrix {
+        let mut identity = Matrix::zero(size);
+        (0..size).for_each(|i| identity[(i, i)] = 1);
+        identity
+    }
+
+    pub fn from_fn<F: FnMut(usize, usize) -> u128>(size: usize, mut cell: F) -> Matrix {
+        Matrix {
+            size,
+            cells: (0..size * size).map(|i| cell(i / size, i % size)).collect(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn mul(&self, other: &Matrix, modulus: Option<u128>) -> Option<Matrix> {
+        assert_eq!(self.size, other.size, "Multiplying mismatched matrices");
+        assert!(
+            modulus.is_none_or(|m| m > 0 && m <= 1 << 64),
+            "Modulus out of range"
+        );
+        let mut product = Matrix::zero(self.size);
+        for row in 0..self.size {
+            for column in 0..self.size {
+                product[(row, column)] = (0..self.size).try_fold(0, |total, k| {
+                    multiply_add(total, self[(row, k)], other[(k, column)], modulus)
+                })?;
+            }
+        }
+        Some(product)
+    }
+
+    /// The matrix raised to `exponent` by repeated squaring, which takes a multiplication or two
+    /// for each bit of the exponent rather than one for each step
+    pub fn pow(&self, mut exponent: u64, modulus: Option<u128>) -> Option<Matrix> {
+        let mut result = Matrix::identity(self.size);
+        if let Some(m) = modulus {
+            result.cells.iter_mut().for_each(|c| *c %= m);
+        }
+        let mut square = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&square, modulus)?;
+            }
+            exponent >>= 1;
+            // Squaring beyond what's needed could overflow even when the answer wouldn't
+            if exponent > 0 {
+                square = square.mul(&square, modulus)?;
+            }
+        }
+        Some(result)
+    }
+
+    /// The matrix times a column vector
+    pub fn apply(&self, vector: &[u128], modulus: Option<u128>) -> Option<Vec<u128>> {
+        assert_eq!(self.size, vector.len(), "Applying to a mismatched vector");
+        (0..self.size)
+            .map(|row| {
+                (0..self.size).try_fold(0, |total, k| {
+                    multiply_add(total, self[(row, k)], vector[k], modulus)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+fn fibonacci() -> Matrix {
+    Matrix::from_fn(2, |row, column| if row + column < 2 { 1 } else { 0 })
+}
+
+#[test]
+fn test_mul() {
+    let a = Matrix::from_fn(2, |row, column| (row * 2 + column) as u128);
+    let squared = a.mul(&a, None).unwrap();
+    assert_eq!(Matrix::from_fn(2, |r, c| [[2, 3], [6, 11]][r][c]), squared);
+    assert_eq!(Some(a.clone()), a.mul(&Matrix::identity(2), None));
+    assert_eq!(Some(vec![1, 11]), a.apply(&[4, 1], None));
+    assert_eq!(Some(vec![1, 2]), a.apply(&[4, 1], Some(3)));
+}
+
+#[test]
+fn test_pow() {
+    // The top right of the nth power of [[1, 1], [1, 0]] is the nth Fibonacci number
+    assert_eq!(55, fibonacci().pow(10, None).unwrap()[(0, 1)]);
+    assert_eq!(
+        2_880_067_194_370_816_120,
+        fibonacci().pow(90, None).unwrap()[(0, 1)]
+    );
+    assert_eq!(Matrix::identity(2), fibonacci().pow(0, None).unwrap());
+    // Well past the largest Fibonacci number that fits in a u128
+    assert_eq!(None, fibonacci().pow(200, None));
+
+    let modulus = Some(1_000_000_007);
+    let half = fibonacci().pow(500_000_000_000_000_000, modulus).unwrap();
+    assert_eq!(
+        half.mul(&half, modulus),
+        fibonacci().pow(1_000_000_000_000_000_000, modulus)
+    );
+    assert_eq!(55, fibonacci().pow(10, modulus).unwrap()[(0, 1)]);
+}
@@ -0,0 +1,165 @@
+// Everything here works in i128 so the products of two i64 sized values, which modular arithmetic
+// is full of, can't overflow
+
+#[allow(dead_code)]
+pub fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[allow(dead_code)]
+pub fn lcm(a: i128, b: i128) -> i128 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        (a / gcd(a, b) * b).abs()
+    }
+}
+
+/// The gcd of `a` and `b` along with `x` and `y` such that `a * x + b * y` is that gcd
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a.abs(), a.signum(), 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// `a` modulo `m`, always between 0 and `m` even when `a` is negative
+pub fn modulo(a: i128, m: i128) -> i128 {
+    a.rem_euclid(m)
+}
+
+/// `a * b` modulo `m`, falling back to doubling and adding when the product itself wouldn't fit
+pub fn mod_mul(a: i128, b: i128, m: i128) -> i128 {
+    let (mut a, mut b) = (modulo(a, m), modulo(b, m));
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    result
+}
+
+/// The `x` between 0 and `m` where `a * x` is 1 modulo `m`, which only exists when `a` and `m`
+/// share no factors
+#[allow(dead_code)]
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    match extended_gcd(modulo(a, m), m) {
+        (1, x, _) => Some(modulo(x, m)),
+        _ => None,
+    }
+}
+
+/// `base` to the power `exponent`, modulo `m`, by repeated squaring
+#[allow(dead_code)]
+pub fn mod_pow(base: i128, mut exponent: u64, m: i128) -> i128 {
+    let mut result = 1 % m;
+    let mut base = modulo(base, m);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// The `(residue, modulus)` that every number meeting all the congruences `x ≡ residue (mod
+/// modulus)` has in common, so they're exactly the numbers `residue + k * modulus`
+///
+/// Unlike the textbook Chinese Remainder Theorem the moduli don't need to be coprime. Moduli that
+/// share factors can ask for contradictory things though, like an odd number that's also even, in
+/// which case it's the first congruence that can't be met that's reported.
+pub fn crt(congruences: &[(i128, i128)]) -> Result<(i128, i128), String> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(residue, modulus), &(r, m)| {
+            assert!(m > 0, "Modulus {} should be positive", m);
+            let (g, p, _) = extended_gcd(modulus, m);
+            let difference = r - residue;
+            if difference % g != 0 {
+                return Err(format!(
+                    "x ≡ {} (mod {}) contradicts x ≡ {} (mod {})",
+                    r, m, residue, modulus
+                ));
+            }
+
+            // modulus * p ≡ g (mod m), so stepping on by modulus * p * difference / g lands on r
+            // while staying on residue modulo modulus
+            let combined = modulus / g * m;
+            let steps = mod_mul(difference / g, p, m / g);
+            Ok((modulo(residue + modulus * steps, combined), combined))
+        })
+}
+
+#[test]
+fn test_gcd_and_lcm() {
+    assert_eq!(6, gcd(48, 18));
+    assert_eq!(6, gcd(-48, 18));
+    assert_eq!(5, gcd(0, 5));
+    assert_eq!(144, lcm(48, 18));
+    assert_eq!(0, lcm(0, 7));
+
+    for (a, b) in [(240, 46), (-7, 3), (17, 0), (0, 9)] {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(gcd(a, b), g);
+        assert_eq!(g, a * x + b * y);
+    }
+}
+
+#[test]
+fn test_modular_arithmetic() {
+    assert_eq!(3, modulo(-7, 5));
+    assert_eq!(Some(4), mod_inverse(3, 11));
+    assert_eq!(Some(7), mod_inverse(-3, 11));
+    assert_eq!(None, mod_inverse(4, 10));
+    assert_eq!(445, mod_pow(4, 13, 497));
+    assert_eq!(0, mod_pow(5, 0, 1));
+    // Big enough that squaring in i64 would overflow
+    let p = 1_000_000_000_000_000_003;
+    assert_eq!(1, mod_pow(2, p as u64 - 1, p));
+    // Big enough that squaring in i128 would overflow
+    let big = (1 << 100) + 1;
+    assert_eq!(big - 1, mod_mul(-1, 1, big));
+    assert_eq!(1, mod_mul(big - 1, big - 1, big));
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(Ok((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+    // Moduli sharing a factor of two
+    assert_eq!(Ok((10, 12)), crt(&[(2, 4), (4, 6)]));
+    assert!(crt(&[(1, 4), (2, 6)]).is_err());
+    assert_eq!(Ok((0, 1)), crt(&[]));
+    // Products past a u64
+    let (a, b) = (1_000_000_007, 998_244_353);
+    let (c, d) = (1_000_000_009, 999_999_937);
+    let (residue, modulus) = crt(&[(5, a), (7, b), (11, c), (13, d)]).unwrap();
+    assert_eq!(a * b * c * d, modulus);
+    assert_eq!(
+        vec![5, 7, 11, 13],
+        vec![residue % a, residue % b, residue % c, residue % d]
+    );
+    // Moduli near 2^62 and beyond, where the step count has to be reduced before it's multiplied
+    let (a, b) = ((1 << 62) - 1, (1 << 62) + 1);
+    let (residue, modulus) = crt(&[(a - 2, a), (b - 3, b)]).unwrap();
+    assert_eq!(a * b, modulus);
+    assert_eq!((a - 2, b - 3), (residue % a, residue % b));
+    let c = (1 << 66) + 1;
+    let (residue, modulus) = crt(&[(3, 7), (c - 1, c)]).unwrap();
+    assert_eq!(7 * c, modulus);
+    assert_eq!((3, c - 1), (residue % 7, residue % c));
+}
@@ -0,0 +1,273 @@
+use grid::Grid;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{char, digit1, one_of, space0, space1};
+use nom::combinator::{all_consuming, map, map_res, opt, recognize};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, separated_pair, tuple};
+use nom::IResult;
+use point::Point2;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where in the input parsing went wrong, and what was expected there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Counting from 1, as editors do
+    pub line: usize,
+    /// Counting from 1, in characters rather than bytes
+    pub column: usize,
+    pub expected: String,
+    /// The whole of the line that failed
+    pub text: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {} in `{}`",
+            self.line, self.column, self.expected, self.text
+        )
+    }
+}
+
+impl ParseError {
+    fn at(line: &str, remaining: &str, expected: String) -> ParseError {
+        let consumed = &line[..line.len() - remaining.len()];
+        ParseError {
+            line: 1,
+            column: consumed.chars().count() + 1,
+            expected,
+            text: line.to_string(),
+        }
+    }
+
+    fn on_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+}
+
+/// A whole number with no sign, like `42`
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// A whole number with an optional `+` or `-` in front, like `-7` or `+3`
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Items separated by commas, allowing spaces either side of each comma
+pub fn comma_separated<'a, T, P>(item: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    separated_list1(delimited(space0, char(','), space0), item)
+}
+
+/// Items separated by runs of spaces, ignoring any at either end
+#[allow(dead_code)]
+pub fn whitespace_separated<'a, T, P>(item: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    delimited(space0, separated_list1(space1, item), space0)
+}
+
+/// A `key:value` pair, where neither can hold spaces or colons
+#[allow(dead_code)]
+pub fn key_value(input: &str) -> IResult<&str, (&str, &str)> {
+    let word = |input| take_while1(|c: char| !c.is_whitespace() && c != ':')(input);
+    separated_pair(word, char(':'), word)(input)
+}
+
+/// A record of `key:value` pairs separated by spaces, like `ecl:gry pid:860033327`
+#[allow(dead_code)]
+pub fn record(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
+    whitespace_separated(key_value)(input)
+}
+
+/// A pair of signed numbers separated by a comma, like `3,-4`
+pub fn coordinate<T: FromStr>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(signed, tuple((space0, char(','), space0)), signed)(input)
+}
+
+/// An inclusive range of whole numbers with no sign, like `5-11`
+pub fn range<T: FromStr>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(unsigned, char('-'), unsigned)(input)
+}
+
+#[allow(dead_code)]
+pub fn point(input: &str) -> IResult<&str, Point2> {
+    map(coordinate, |(x, y)| Point2::new(x, y))(input)
+}
+
+/// Two things separated by an arrow, like `0,9 -> 5,9`
+pub fn arrow<'a, A, B, P, Q>(from: P, to: Q) -> impl FnMut(&'a str) -> IResult<&'a str, (A, B)>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, A>,
+    Q: FnMut(&'a str) -> IResult<&'a str, B>,
+{
+    separated_pair(from, tag(" -> "), to)
+}
+
+/// Run a parser over the whole of a line, failing if anything is left over
+pub fn parse_line<'a, T, P>(line: &'a str, parser: P) -> Result<T, ParseError>
+where
+    P: FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    match all_consuming(parser)(line) {
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(ParseError::at(
+            line,
+            e.input,
+            e.code.description().to_lowercase(),
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(line, "", "more input".to_string())),
+    }
+}
+
+/// Run a parser over every line, reporting which line failed if any do
+pub fn parse_lines<T, P>(lines: &[String], mut parser: P) -> Result<Vec<T>, ParseError>
+where
+    P: for<'a> FnMut(&'a str) -> IResult<&'a str, T>,
+{
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse_line(line, &mut parser).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// A character map, one line per row, where `cell` turns each character into a cell or rejects
+/// it with `None`
+#[allow(dead_code)]
+pub fn grid<T, F>(lines: &[String], mut cell: F) -> Result<Grid<T>, ParseError>
+where
+    F: FnMut(char) -> Option<T>,
+{
+    let width = lines.first().map_or(0, |l| l.chars().count());
+    let mut cells = Vec::with_capacity(width * lines.len());
+    for (y, line) in lines.iter().enumerate() {
+        let error = |column: usize, expected: String| ParseError {
+            line: y + 1,
+            column: column + 1,
+            expected,
+            text: line.to_string(),
+        };
+        for (x, c) in line.chars().enumerate() {
+            if x == width {
+                return Err(error(x, format!("the row to end after {} cells", width)));
+            }
+            match cell(c) {
+                Some(value) => cells.push(value),
+                None => return Err(error(x, "a known cell".to_string())),
+            }
+        }
+        let row = line.chars().count();
+        if row < width {
+            return Err(error(row, format!("{} cells in the row", width)));
+        }
+    }
+
+    let mut cells = cells.into_iter();
+    Ok(Grid::from_fn(width, lines.len(), |_, _| {
+        cells.next().unwrap()
+    }))
+}
+
+/// Split the input into the blocks between blank lines, dropping any blocks left empty by
+/// repeated blank lines
+pub fn blocks(lines: &[String]) -> Vec<&[String]> {
+    lines
+        .split(|l| l.trim().is_empty())
+        .filter(|block| !block.is_empty())
+        .collect()
+}
+
+#[test]
+fn test_numbers() {
+    assert_eq!(Ok(("", 123)), signed::<isize>("+123"));
+    assert_eq!(Ok(("", -123)), signed::<isize>("-123"));
+    assert_eq!(Ok((" x", 45)), signed::<isize>("45 x"));
+    assert_eq!(Ok(("", 42)), unsigned::<usize>("42"));
+    assert!(unsigned::<usize>("-42").is_err());
+    // Too big for the type asked for
+    assert!(unsigned::<u8>("256").is_err());
+}
+
+#[test]
+fn test_lists() {
+    assert_eq!(
+        Ok(vec![1, -2, 3]),
+        parse_line("1,-2, 3", comma_separated(signed::<isize>))
+    );
+    assert_eq!(
+        Ok(vec![7, 8, 9]),
+        parse_line("  7 8   9 ", whitespace_separated(unsigned::<usize>))
+    );
+    assert_eq!(
+        Ok(vec![("ecl", "gry"), ("pid", "#8600")]),
+        parse_line("ecl:gry pid:#8600", record)
+    );
+}
+
+#[test]
+fn test_coordinates() {
+    assert_eq!(
+        Ok((Point2::new(0, 9), Point2::new(-5, 9))),
+        parse_line("0,9 -> -5,9", arrow(point, point))
+    );
+    assert_eq!(Ok((3, 4)), parse_line("3, 4", coordinate::<isize>));
+    assert_eq!(Ok((5, 11)), parse_line("5-11", range::<usize>));
+    assert!(parse_line("5--11", range::<usize>).is_err());
+}
+
+#[test]
+fn test_errors() {
+    let input: Vec<String> = vec!["1,2".to_string(), "3,4".to_string(), "5;6".to_string()];
+    let error = parse_lines(&input, coordinate::<isize>).unwrap_err();
+    assert_eq!(3, error.line);
+    assert_eq!(2, error.column);
+    assert_eq!(
+        "line 3, column 2: expected char in `5;6`",
+        error.to_string()
+    );
+
+    // Left over input is an error too
+    let error = parse_line("12ab", unsigned::<usize>).unwrap_err();
+    assert_eq!(3, error.column);
+    assert_eq!("end of file", error.expected);
+}
+
+#[test]
+fn test_grid() {
+    let cell = |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    };
+    let lines: Vec<String> = vec!["..#".to_string(), "#..".to_string()];
+    let parsed = grid(&lines, cell).unwrap();
+    assert_eq!((3, 2), (parsed.width(), parsed.height()));
+    assert!(parsed[(2, 0)] && parsed[(0, 1)] && !parsed[(1, 1)]);
+
+    let unknown = grid(&["..#".to_string(), "#.?".to_string()], cell).unwrap_err();
+    assert_eq!((2, 3), (unknown.line, unknown.column));
+    let ragged = grid(&["..#".to_string(), "#.".to_string()], cell).unwrap_err();
+    assert_eq!((2, 3), (ragged.line, ragged.column));
+    let ragged = grid(&["..#".to_string(), "#...".to_string()], cell).unwrap_err();
+    assert_eq!((2, 4), (ragged.line, ragged.column));
+}
+
+#[test]
+fn test_blocks() {
+    let lines: Vec<String> = ["a", "b", "", "c", "", "", "d", ""]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+    let blocks = blocks(&lines);
+    assert_eq!(3, blocks.len());
+    assert_eq!(&["a".to_string(), "b".to_string()], blocks[0]);
+    assert_eq!(&["d".to_string()], blocks[2]);
+}
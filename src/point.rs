@@ -0,0 +1,247 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position on a 2D plane, with `y` growing downwards as it does reading a puzzle's map
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// A step or offset between two `Point2`s
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub fn new(x: isize, y: isize) -> Point2 {
+        Point2 { x, y }
+    }
+
+    /// Distance moving only along the axes, like a taxi around city blocks
+    pub fn manhattan(&self, other: Point2) -> isize {
+        (*self - other).manhattan()
+    }
+
+    /// Distance moving diagonally as well, like a king on a chess board
+    pub fn chebyshev(&self, other: Point2) -> isize {
+        (*self - other).chebyshev()
+    }
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub fn new(x: isize, y: isize) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn manhattan(&self) -> isize {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(&self) -> isize {
+        self.x.abs().max(self.y.abs())
+    }
+
+    /// Rotate about the origin by a number of quarter turns, clockwise when positive
+    pub fn rotate(&self, quarter_turns: isize) -> Vec2 {
+        match quarter_turns.rem_euclid(4) {
+            0 => *self,
+            1 => Vec2::new(-self.y, self.x),
+            2 => Vec2::new(-self.x, -self.y),
+            _ => Vec2::new(self.y, -self.x),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn turn_right(&self) -> Vec2 {
+        self.rotate(1)
+    }
+
+    #[allow(dead_code)]
+    pub fn turn_left(&self) -> Vec2 {
+        self.rotate(-1)
+    }
+}
+
+impl Add<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn add(self, v: Vec2) -> Point2 {
+        Point2::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point2 {
+    fn add_assign(&mut self, v: Vec2) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vec2> for Point2 {
+    type Output = Point2;
+
+    fn sub(self, v: Vec2) -> Point2 {
+        Point2::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl SubAssign<Vec2> for Point2 {
+    fn sub_assign(&mut self, v: Vec2) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Point2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, scale: isize) -> Vec2 {
+        Vec2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+/// One of the four compass directions, with north being up the page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Parse any of the ways puzzles write a direction: `N/E/S/W`, `U/R/D/L` or `^>v<`
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            'N' | 'U' | '^' => Some(Direction::North),
+            'E' | 'R' | '>' => Some(Direction::East),
+            'S' | 'D' | 'v' => Some(Direction::South),
+            'W' | 'L' | '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// A single step in this direction
+    pub fn vector(&self) -> Vec2 {
+        match self {
+            Direction::North => Vec2::new(0, -1),
+            Direction::East => Vec2::new(1, 0),
+            Direction::South => Vec2::new(0, 1),
+            Direction::West => Vec2::new(-1, 0),
+        }
+    }
+
+    /// Turn by a number of quarter turns, clockwise when positive
+    pub fn rotate(&self, quarter_turns: isize) -> Direction {
+        let index = Direction::ALL.iter().position(|d| d == self).unwrap() as isize;
+        Direction::ALL[(index + quarter_turns).rem_euclid(4) as usize]
+    }
+
+    #[allow(dead_code)]
+    pub fn turn_right(&self) -> Direction {
+        self.rotate(1)
+    }
+
+    #[allow(dead_code)]
+    pub fn turn_left(&self) -> Direction {
+        self.rotate(-1)
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    let p = Point2::new(3, -2);
+    let v = Vec2::new(1, 4);
+    assert_eq!(Point2::new(4, 2), p + v);
+    assert_eq!(Point2::new(2, -6), p - v);
+    assert_eq!(Vec2::new(3, -2), p - Point2::ORIGIN);
+    assert_eq!(Vec2::new(-3, -12), -v * 3);
+    assert_eq!(Vec2::new(2, 8), v + v);
+}
+
+#[test]
+fn test_distances() {
+    let a = Point2::new(1, 1);
+    let b = Point2::new(4, -1);
+    assert_eq!(5, a.manhattan(b));
+    assert_eq!(3, a.chebyshev(b));
+    assert_eq!(a.manhattan(b), b.manhattan(a));
+}
+
+#[test]
+fn test_rotation() {
+    let east = Vec2::new(10, -4);
+    assert_eq!(Vec2::new(4, 10), east.turn_right());
+    assert_eq!(Vec2::new(-4, -10), east.turn_left());
+    assert_eq!(east.rotate(-3), east.rotate(1));
+    assert_eq!(east, east.rotate(8));
+
+    assert_eq!(Direction::West, Direction::North.turn_left());
+    assert_eq!(Direction::South, Direction::East.turn_right());
+    assert_eq!(Direction::North, Direction::North.rotate(-4));
+    for direction in Direction::ALL.iter() {
+        assert_eq!(
+            direction.vector().turn_right(),
+            direction.turn_right().vector()
+        );
+    }
+}
+
+#[test]
+fn test_direction_from_char() {
+    assert_eq!(Some(Direction::North), Direction::from_char('U'));
+    assert_eq!(Some(Direction::East), Direction::from_char('>'));
+    assert_eq!(Some(Direction::South), Direction::from_char('S'));
+    assert_eq!(Some(Direction::West), Direction::from_char('L'));
+    assert_eq!(None, Direction::from_char('F'));
+}
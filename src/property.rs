@@ -0,0 +1,167 @@
+use rng::Rng;
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+
+/// How many generated inputs each property is checked against, override with `AOC_PROPTEST_CASES`
+const DEFAULT_CASES: u64 = 100;
+/// Where case seeds start from, override with `AOC_PROPTEST_SEED` to explore or replay a failure
+const DEFAULT_SEED: u64 = 2020;
+
+/// A property holds with `Ok`, or describes what went wrong with `Err`. Properties with
+/// preconditions on their input should return `Ok` for inputs that don't meet them, which also
+/// stops shrinking from wandering into invalid puzzle inputs.
+pub type Verdict = Result<(), String>;
+
+fn env_or(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Run a property, treating a panic (e.g. a solution choking on its input) as a failure
+fn evaluate<P>(property: &P, lines: &[String]) -> Verdict
+where
+    P: Fn(&[String]) -> Verdict,
+{
+    match panic::catch_unwind(AssertUnwindSafe(|| property(lines))) {
+        Ok(verdict) => verdict,
+        Err(e) => Err(format!(
+            "panicked: {}",
+            e.downcast_ref::<String>()
+                .cloned()
+                .or_else(|| e.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_else(|| "unknown panic".to_string())
+        )),
+    }
+}
+
+/// Cut a failing input down by removing ever smaller runs of lines while the property still
+/// fails, returning the smallest input found along with its failure
+pub fn shrink<P>(mut lines: Vec<String>, mut reason: String, property: P) -> (Vec<String>, String)
+where
+    P: Fn(&[String]) -> Verdict,
+{
+    let mut chunk = lines.len().div_ceil(2);
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed_any = false;
+        while start + chunk <= lines.len() {
+            let mut candidate = lines.clone();
+            candidate.drain(start..start + chunk);
+            match evaluate(&property, &candidate) {
+                Err(r) => {
+                    lines = candidate;
+                    reason = r;
+                    removed_any = true;
+                }
+                Ok(()) => start += chunk,
+            }
+        }
+
+        // Keep trying chunks this size until none can go, then try finer ones
+        if !removed_any {
+            chunk /= 2;
+        }
+    }
+
+    (lines, reason)
+}
+
+/// Check a property against many generated line-based puzzle inputs, panicking with a shrunk
+/// counterexample and the seed needed to reproduce it if any input fails
+pub fn check<G, P>(name: &str, generate: G, property: P)
+where
+    G: Fn(&mut Rng) -> Vec<String>,
+    P: Fn(&[String]) -> Verdict,
+{
+    let cases = env_or("AOC_PROPTEST_CASES", DEFAULT_CASES);
+    let seed = env_or("AOC_PROPTEST_SEED", DEFAULT_SEED);
+
+    for case in 0..cases {
+        let case_seed = seed.wrapping_add(case);
+        let lines = generate(&mut Rng::new(case_seed));
+
+        if let Err(reason) = evaluate(&property, &lines) {
+            let original_len = lines.len();
+            let (shrunk, reason) = shrink(lines, reason, &property);
+            panic!(
+                "Property '{}' failed for case seed {} (AOC_PROPTEST_SEED={} case {}): {}\n\
+                 Shrunk from {} to {} lines:\n{}",
+                name,
+                case_seed,
+                seed,
+                case,
+                reason,
+                original_len,
+                shrunk.len(),
+                shrunk.join("\n")
+            );
+        }
+    }
+}
+
+#[test]
+fn test_shrink_finds_single_bad_line() {
+    let lines: Vec<String> = (0..40)
+        .map(|i| if i == 27 { "bad" } else { "good" }.to_string())
+        .collect();
+    let no_bad_lines = |lines: &[String]| -> Verdict {
+        match lines.iter().find(|l| l.as_str() == "bad") {
+            Some(l) => Err(format!("found {}", l)),
+            None => Ok(()),
+        }
+    };
+
+    let (shrunk, reason) = shrink(lines, "found bad".to_string(), no_bad_lines);
+    assert_eq!(vec!["bad".to_string()], shrunk);
+    assert_eq!("found bad", reason);
+}
+
+#[test]
+fn test_shrink_keeps_lines_needed_together() {
+    let lines: Vec<String> = (0..30).map(|i| i.to_string()).collect();
+    let not_both = |lines: &[String]| -> Verdict {
+        let has = |s: &str| lines.iter().any(|l| l == s);
+        if has("3") && has("17") {
+            Err("3 and 17 together".to_string())
+        } else {
+            Ok(())
+        }
+    };
+
+    let (shrunk, _) = shrink(lines, String::new(), not_both);
+    assert_eq!(vec!["3".to_string(), "17".to_string()], shrunk);
+}
+
+#[test]
+fn test_shrink_treats_panics_as_failures() {
+    let lines: Vec<String> = vec!["1".to_string(), "x".to_string(), "2".to_string()];
+    let all_numbers = |lines: &[String]| -> Verdict {
+        lines.iter().for_each(|l| {
+            l.parse::<usize>().unwrap();
+        });
+        Ok(())
+    };
+
+    let (shrunk, reason) = shrink(lines, String::new(), all_numbers);
+    assert_eq!(vec!["x".to_string()], shrunk);
+    assert!(reason.starts_with("panicked"), "{}", reason);
+}
+
+#[test]
+fn test_check_passes_when_property_holds() {
+    check(
+        "generated numbers parse",
+        |rng| {
+            (0..rng.below(10))
+                .map(|_| rng.below(1000).to_string())
+                .collect()
+        },
+        |lines| {
+            lines
+                .iter()
+                .try_for_each(|l| l.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+        },
+    );
+}
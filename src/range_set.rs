@@ -0,0 +1,145 @@
+/// A set of whole numbers stored as inclusive ranges, kept sorted with any overlapping or touching
+/// ranges merged together, so `1-3 or 4-7` is stored as just `1-7`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RangeSet {
+    ranges: Vec<(isize, isize)>,
+}
+
+impl RangeSet {
+    pub fn new() -> RangeSet {
+        RangeSet { ranges: vec![] }
+    }
+
+    pub fn from_ranges<I>(ranges: I) -> RangeSet
+    where
+        I: IntoIterator<Item = (isize, isize)>,
+    {
+        let mut set = RangeSet::new();
+        ranges
+            .into_iter()
+            .for_each(|(low, high)| set.insert(low, high));
+        set
+    }
+
+    /// Add every number from `low` to `high` inclusive
+    pub fn insert(&mut self, low: isize, high: isize) {
+        assert!(low <= high, "Empty range {}-{}", low, high);
+        self.ranges.push((low, high));
+        self.ranges.sort_unstable();
+
+        let mut merged: Vec<(isize, isize)> = Vec::with_capacity(self.ranges.len());
+        for &(low, high) in &self.ranges {
+            match merged.last_mut() {
+                Some(last) if low <= last.1.saturating_add(1) => last.1 = last.1.max(high),
+                _ => merged.push((low, high)),
+            }
+        }
+        self.ranges = merged;
+    }
+
+    /// The merged ranges, lowest first
+    pub fn ranges(&self) -> &[(isize, isize)] {
+        &self.ranges
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: isize) -> bool {
+        // The ranges are sorted and don't overlap, so only the last one starting at or before the
+        // value could hold it
+        match self.ranges.binary_search_by_key(&value, |r| r.0) {
+            Ok(_) => true,
+            Err(0) => false,
+            Err(i) => value <= self.ranges[i - 1].1,
+        }
+    }
+
+    /// How many numbers are in the set
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|(low, high)| (high - low) as usize + 1)
+            .sum()
+    }
+
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        RangeSet::from_ranges(self.ranges.iter().chain(other.ranges.iter()).cloned())
+    }
+
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            let (low, high) = (a.0.max(b.0), a.1.min(b.1));
+            if low <= high {
+                ranges.push((low, high));
+            }
+            // Whichever range ends first can't overlap anything further along the other set
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    /// Every number from `low` to `high` inclusive that isn't in the set
+    #[allow(dead_code)]
+    pub fn complement_within(&self, low: isize, high: isize) -> RangeSet {
+        let mut ranges = vec![];
+        let mut next = low;
+        for &(start, end) in &self.ranges {
+            if start > high {
+                break;
+            }
+            if start > next {
+                ranges.push((next, start - 1));
+            }
+            next = next.max(end.saturating_add(1));
+        }
+        if next <= high {
+            ranges.push((next, high));
+        }
+        RangeSet { ranges }
+    }
+}
+
+#[test]
+fn test_merging() {
+    let set = RangeSet::from_ranges(vec![(5, 7), (1, 3), (4, 4), (10, 12), (11, 20)]);
+    assert_eq!(&[(1, 7), (10, 20)], set.ranges());
+    assert_eq!(18, set.len());
+    assert!(RangeSet::new().is_empty());
+}
+
+#[test]
+fn test_contains() {
+    let set = RangeSet::from_ranges(vec![(1, 3), (5, 7)]);
+    assert!(set.contains(1));
+    assert!(set.contains(3));
+    assert!(set.contains(5));
+    assert!(!set.contains(4));
+    assert!(!set.contains(0));
+    assert!(!set.contains(8));
+}
+
+#[test]
+fn test_set_operations() {
+    let a = RangeSet::from_ranges(vec![(1, 3), (5, 11)]);
+    let b = RangeSet::from_ranges(vec![(2, 6), (9, 20)]);
+    assert_eq!(&[(1, 20)], a.union(&b).ranges());
+    assert_eq!(&[(2, 3), (5, 6), (9, 11)], a.intersection(&b).ranges());
+    assert_eq!(
+        &[(0, 0), (4, 4), (12, 15)],
+        a.complement_within(0, 15).ranges()
+    );
+    assert_eq!(&[(4, 4)], a.complement_within(2, 10).ranges());
+    assert!(a.complement_within(6, 8).is_empty());
+}
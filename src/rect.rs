@@ -0,0 +1,174 @@
+use point::Point2;
+
+/// An axis-aligned rectangle of whole cells, covering `min` up to but not including `max`
+///
+/// Keeping the far edges exclusive means a rectangle's width is just `max.x - min.x`, and two
+/// rectangles that only touch along an edge don't overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point2,
+    pub max: Point2,
+}
+
+impl Rect {
+    /// The rectangle with its top left corner at `corner` stretching `width` by `height` cells
+    pub fn new(corner: Point2, width: isize, height: isize) -> Rect {
+        assert!(width >= 0 && height >= 0, "Negative sized rectangle");
+        Rect {
+            min: corner,
+            max: Point2::new(corner.x + width, corner.y + height),
+        }
+    }
+
+    pub fn width(&self) -> isize {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> isize {
+        self.max.y - self.min.y
+    }
+
+    pub fn area(&self) -> isize {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.area() == 0
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, p: Point2) -> bool {
+        self.min.x <= p.x && p.x < self.max.x && self.min.y <= p.y && p.y < self.max.y
+    }
+
+    /// The cells covered by both rectangles, if they share any
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let min = Point2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        if min.x < max.x && min.y < max.y {
+            Some(Rect { min, max })
+        } else {
+            None
+        }
+    }
+
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+}
+
+/// The area covered by at least `depth` of the rectangles, so 1 gives the area of their union and 2
+/// the area where any of them overlap
+///
+/// Sweeps a line across the x axis stopping only where a rectangle starts or ends, keeping count of
+/// how many rectangles cover each of the distinct bands between y edges. The work depends on the
+/// number of rectangles rather than their size, so huge rectangles cost no more than small ones.
+pub fn covered_area(rects: &[Rect], depth: usize) -> isize {
+    let mut ys: Vec<isize> = rects.iter().flat_map(|r| vec![r.min.y, r.max.y]).collect();
+    ys.sort_unstable();
+    ys.dedup();
+    let band = |y: isize| ys.binary_search(&y).unwrap();
+
+    // Where each rectangle starts and stops covering its bands as the line sweeps past
+    let mut events: Vec<(isize, isize, usize, usize)> = rects
+        .iter()
+        .filter(|r| !r.is_empty())
+        .flat_map(|r| {
+            let (top, bottom) = (band(r.min.y), band(r.max.y));
+            vec![(r.min.x, 1, top, bottom), (r.max.x, -1, top, bottom)]
+        })
+        .collect();
+    events.sort_unstable();
+
+    let mut coverage = vec![0isize; ys.len()];
+    let mut area = 0;
+    let mut last_x = events.first().map_or(0, |e| e.0);
+    for (x, change, top, bottom) in events {
+        let covered_height: isize = (0..ys.len().saturating_sub(1))
+            .filter(|b| coverage[*b] >= depth as isize)
+            .map(|b| ys[b + 1] - ys[b])
+            .sum();
+        area += covered_height * (x - last_x);
+        last_x = x;
+
+        coverage[top..bottom].iter_mut().for_each(|c| *c += change);
+    }
+
+    area
+}
+
+/// The indices of the rectangles that don't overlap any of the others
+///
+/// Sweeps across the x axis so each rectangle is only checked against the ones it could possibly
+/// reach, rather than against every other rectangle.
+pub fn isolated(rects: &[Rect]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..rects.len()).collect();
+    order.sort_unstable_by_key(|i| rects[*i].min.x);
+
+    let mut overlapping = vec![false; rects.len()];
+    let mut active: Vec<usize> = vec![];
+    for i in order {
+        let rect = rects[i];
+        active.retain(|a| rects[*a].max.x > rect.min.x);
+        for a in &active {
+            if rects[*a].overlaps(&rect) {
+                overlapping[*a] = true;
+                overlapping[i] = true;
+            }
+        }
+        active.push(i);
+    }
+
+    (0..rects.len()).filter(|i| !overlapping[*i]).collect()
+}
+
+#[cfg(test)]
+fn rect(x: isize, y: isize, w: isize, h: isize) -> Rect {
+    Rect::new(Point2::new(x, y), w, h)
+}
+
+#[test]
+fn test_intersection() {
+    assert_eq!(
+        Some(rect(3, 3, 2, 2)),
+        rect(1, 3, 4, 4).intersection(&rect(3, 1, 4, 4))
+    );
+    // Sharing an edge isn't overlapping
+    assert_eq!(None, rect(0, 0, 2, 2).intersection(&rect(2, 0, 2, 2)));
+    assert!(rect(0, 0, 2, 2).contains(Point2::new(1, 1)));
+    assert!(!rect(0, 0, 2, 2).contains(Point2::new(2, 1)));
+}
+
+#[test]
+fn test_covered_area() {
+    let rects = [rect(1, 3, 4, 4), rect(3, 1, 4, 4), rect(5, 5, 2, 2)];
+    assert_eq!(4, covered_area(&rects, 2));
+    assert_eq!(32, covered_area(&rects, 1));
+    assert_eq!(0, covered_area(&rects, 3));
+    assert_eq!(0, covered_area(&[], 1));
+
+    // Three deep in the middle
+    let stacked = [rect(0, 0, 3, 3), rect(1, 1, 3, 3), rect(1, 1, 1, 1)];
+    assert_eq!(1, covered_area(&stacked, 3));
+    assert_eq!(4, covered_area(&stacked, 2));
+}
+
+#[test]
+fn test_covered_area_of_huge_rectangles() {
+    let rects = [
+        rect(0, 0, 3_000_000, 2_000_000),
+        rect(1_000_000, 1_000_000, 4_000_000, 4_000_000),
+    ];
+    assert_eq!(2_000_000_000_000, covered_area(&rects, 2));
+    assert_eq!(
+        6_000_000_000_000 + 16_000_000_000_000 - 2_000_000_000_000,
+        covered_area(&rects, 1)
+    );
+}
+
+#[test]
+fn test_isolated() {
+    let rects = [rect(1, 3, 4, 4), rect(3, 1, 4, 4), rect(5, 5, 2, 2)];
+    assert_eq!(vec![2], isolated(&rects));
+    assert_eq!(vec![0, 1], isolated(&[rect(0, 0, 2, 2), rect(2, 0, 2, 2)]));
+}
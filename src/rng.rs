@@ -0,0 +1,82 @@
+/// A small seeded pseudo-random number generator (SplitMix64)
+///
+/// Not remotely cryptographic, but fast, dependency free and deterministic, so anything generated
+/// from it can be reproduced exactly from the seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Can't pick a value below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// Uniform value in the inclusive range `low..=high`
+    pub fn range(&mut self, low: isize, high: isize) -> isize {
+        assert!(low <= high, "Empty range {}..={}", low, high);
+        low + self.below((high - low) as usize + 1) as isize
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Fisher-Yates shuffle in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[test]
+fn test_same_seed_same_sequence() {
+    let mut a = Rng::new(2020);
+    let mut b = Rng::new(2020);
+    let mut c = Rng::new(2021);
+
+    let from_a: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+    let from_b: Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+    let from_c: Vec<u64> = (0..10).map(|_| c.next_u64()).collect();
+
+    assert_eq!(from_a, from_b);
+    assert_ne!(from_a, from_c);
+}
+
+#[test]
+fn test_ranges_stay_in_bounds() {
+    let mut rng = Rng::new(1);
+    for _ in 0..1000 {
+        assert!(rng.below(7) < 7);
+        let r = rng.range(-3, 3);
+        assert!((-3..=3).contains(&r));
+    }
+    assert_eq!(5, rng.range(5, 5));
+}
+
+#[test]
+fn test_shuffle_keeps_every_item() {
+    let mut rng = Rng::new(7);
+    let mut items: Vec<usize> = (0..50).collect();
+    rng.shuffle(&mut items);
+    assert_ne!((0..50).collect::<Vec<usize>>(), items);
+
+    items.sort_unstable();
+    assert_eq!((0..50).collect::<Vec<usize>>(), items);
+}
@@ -0,0 +1,417 @@
+use alloc::{self, Allocations};
+use aoc2017;
+use aoc2018;
+use aoc2019;
+use aoc2020;
+use aoc2021;
+use aoc2022;
+use aoc2023;
+use chrono::{Datelike, Utc};
+use generate::Generated;
+use rng::Rng;
+use std::fmt;
+use util;
+
+/// Where a day's puzzle input comes from
+pub enum Input {
+    /// A file read line by line, dropping blank lines
+    Lines(&'static str),
+    /// A file read line by line, keeping blank lines for puzzles that group records by them
+    LinesInclBlanks(&'static str),
+    /// Input embedded in the source rather than read from disk
+    Inline(&'static str),
+}
+
+impl Input {
+    pub fn load(&self) -> Vec<String> {
+        match self {
+            Input::Lines(path) => util::read_file_input(path),
+            Input::LinesInclBlanks(path) => util::read_file_input_incl_blanks(path),
+            Input::Inline(input) => input.lines().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    /// Tidy up lines that didn't come from disk, such as generated input, the same way as lines of
+    /// this day's puzzle input are tidied up when read
+    pub fn tidy(&self, lines: Vec<String>) -> Vec<String> {
+        match self {
+            Input::Lines(_) => lines
+                .iter()
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect(),
+            Input::LinesInclBlanks(_) => lines.iter().map(|l| l.trim().to_string()).collect(),
+            Input::Inline(_) => lines,
+        }
+    }
+
+    /// Read another file, such as an example, the same way this day's puzzle input is read
+    pub fn load_file(&self, path: &str) -> Vec<String> {
+        match self {
+            Input::LinesInclBlanks(_) => util::read_file_input_incl_blanks(path),
+            _ => util::read_file_input(path),
+        }
+    }
+}
+
+/// Named numbers that tune how a solution runs without being part of its input, like the size of
+/// 2020 day 9's preamble which is smaller in the examples than in the real puzzle
+///
+/// Solutions pick their own default for anything that hasn't been set.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Params(Vec<(String, usize)>);
+
+impl Params {
+    /// Set a parameter from a `name=value` pair, as given on the command line or in an examples
+    /// manifest
+    pub fn set(&mut self, setting: &str) {
+        let (name, value) = setting
+            .split_once('=')
+            .unwrap_or_else(|| panic!("Parameter {} should look like name=value", setting));
+        let value = value
+            .parse()
+            .unwrap_or_else(|_| panic!("Parameter {} should be a whole number", setting));
+
+        self.0.retain(|(n, _)| n != name);
+        self.0.push((name.to_string(), value));
+    }
+
+    pub fn get(&self, name: &str, default: usize) -> usize {
+        self.0
+            .iter()
+            .find(|(n, _)| n == name)
+            .map_or(default, |(_, value)| *value)
+    }
+}
+
+/// The parameters that have been set, like `preamble=5,rounds=10`, sorted by name so the same
+/// settings always read the same
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut settings: Vec<String> = self
+            .0
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        settings.sort();
+        write!(f, "{}", settings.join(","))
+    }
+}
+
+/// A single part of a day's solution, with its answer rendered for display
+pub type Part = fn(&[String], &Params) -> String;
+
+/// Produces a random but valid puzzle input of roughly the given size for a day
+pub type Generator = fn(&mut Rng, usize) -> Vec<String>;
+
+pub struct Solution {
+    pub year: i32,
+    pub day: u32,
+    pub input: Input,
+    pub part_one: Part,
+    pub part_two: Part,
+    pub generate: Generator,
+}
+
+impl Solution {
+    pub fn parts(&self) -> [(u8, Part); 2] {
+        [(1, self.part_one), (2, self.part_two)]
+    }
+
+    /// The real puzzle input, or a generated one in its place
+    pub fn load_input(&self, generated: Option<Generated>) -> Vec<String> {
+        match generated {
+            Some(generated) => generated.lines(self),
+            None => self.input.load(),
+        }
+    }
+}
+
+pub fn solutions_for_year(year: i32) -> Vec<Solution> {
+    match year {
+        2017 => aoc2017::solutions(),
+        2018 => aoc2018::solutions(),
+        2019 => aoc2019::solutions(),
+        2020 => aoc2020::solutions(),
+        2021 => aoc2021::solutions(),
+        2022 => aoc2022::solutions(),
+        2023 => aoc2023::solutions(),
+        _ => panic!("No advent of code solutions for {}", year),
+    }
+}
+
+pub fn all_solutions() -> Vec<Solution> {
+    (2017..=2023).flat_map(solutions_for_year).collect()
+}
+
+/// Narrow the registry down to an optional year and day, as given on the command line
+pub fn select(year: Option<i32>, day: Option<u32>) -> Vec<Solution> {
+    let solutions = match year {
+        Some(year) => solutions_for_year(year),
+        None => all_solutions(),
+    };
+
+    solutions
+        .into_iter()
+        .filter(|s| day.is_none_or(|d| d == s.day))
+        .collect()
+}
+
+/// How the runner shows its answers
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Report {
+    /// Track what each part allocates and show it alongside the answer, which needs building with
+    /// the `count-allocations` feature
+    pub allocations: bool,
+    /// Print JSON for other tools to pick up rather than text for people
+    pub json: bool,
+}
+
+/// Command line options for running solutions
+///
+/// ```text
+/// [year] [day] [--alloc] [--json] [--param name=value]...
+/// ```
+#[derive(Debug, PartialEq)]
+struct Options {
+    year: i32,
+    day: Option<u32>,
+    params: Params,
+    report: Report,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Options {
+        let mut year = None;
+        let mut day = None;
+        let mut params = Params::default();
+        let mut report = Report::default();
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--alloc" => report.allocations = true,
+                "--json" => report.json = true,
+                "--param" => params.set(
+                    args.next()
+                        .unwrap_or_else(|| panic!("Missing value for {}", arg)),
+                ),
+                positional if year.is_none() => year = Some(positional.parse().unwrap()),
+                positional if day.is_none() => day = Some(positional.parse().unwrap()),
+                unknown => panic!("Unexpected argument: {}", unknown),
+            }
+        }
+
+        Options {
+            year: year.unwrap_or_else(|| Utc::now().year()),
+            day,
+            params,
+            report,
+        }
+    }
+}
+
+/// The answer to a single part, along with what it allocated if that was being tracked
+#[derive(Debug, PartialEq)]
+pub struct Answer {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub allocations: Option<Allocations>,
+}
+
+impl Answer {
+    fn to_json(&self) -> String {
+        let allocations = match self.allocations {
+            Some(a) => format!(
+                "{{\"count\":{},\"bytes\":{},\"peak\":{}}}",
+                a.count, a.bytes, a.peak
+            ),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"allocations\":{}}}",
+            self.year,
+            self.day,
+            self.part,
+            json_string(&self.answer),
+            allocations
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Solve every part of the given solutions, loading or generating each input before any tracking
+/// starts so only the solution's own allocations are counted
+pub fn solve(
+    solutions: &[Solution],
+    generated: Option<Generated>,
+    params: &Params,
+    track_allocations: bool,
+) -> Vec<Answer> {
+    let mut answers = vec![];
+
+    for solution in solutions {
+        let input = solution.load_input(generated);
+        for (part, solve) in solution.parts().iter() {
+            let (answer, allocations) = if track_allocations {
+                let (answer, allocations) = alloc::track(|| solve(&input, params));
+                (answer, Some(allocations))
+            } else {
+                (solve(&input, params), None)
+            };
+            answers.push(Answer {
+                year: solution.year,
+                day: solution.day,
+                part: *part,
+                answer,
+                allocations,
+            });
+        }
+    }
+
+    answers
+}
+
+pub fn print_solutions(
+    year: i32,
+    solutions: &[Solution],
+    generated: Option<Generated>,
+    params: &Params,
+    report: Report,
+) {
+    let answers = solve(solutions, generated, params, report.allocations);
+
+    if report.json {
+        let answers: Vec<String> = answers.iter().map(Answer::to_json).collect();
+        println!("[{}]", answers.join(","));
+        return;
+    }
+
+    println!("Advent of Code {}", year);
+    if report.allocations {
+        println!(
+            "{:>4} {:>4} {:>10} {:>14} {:>14}  Answer",
+            "Day", "Part", "Allocs", "Bytes", "Peak"
+        );
+        for answer in &answers {
+            let allocations = answer.allocations.unwrap_or_default();
+            println!(
+                "{:>4} {:>4} {:>10} {:>14} {:>14}  {}",
+                answer.day,
+                answer.part,
+                allocations.count,
+                allocations.bytes,
+                allocations.peak,
+                answer.answer
+            );
+        }
+    } else {
+        for day in answers.chunks(2) {
+            println!(
+                "\tDay {}: Part One={}, Part Two={}",
+                day[0].day, day[0].answer, day[1].answer
+            );
+        }
+    }
+}
+
+/// Run the solutions picked out on the command line against their real puzzle input
+pub fn run(args: &[String]) {
+    let options = Options::parse(args);
+    let solutions = select(Some(options.year), options.day);
+    print_solutions(
+        options.year,
+        &solutions,
+        None,
+        &options.params,
+        options.report,
+    );
+}
+
+#[test]
+fn test_select_filters_by_year_and_day() {
+    let selected = select(Some(2020), Some(15));
+    assert_eq!(1, selected.len());
+    assert_eq!(2020, selected[0].year);
+    assert_eq!(15, selected[0].day);
+
+    assert!(select(Some(2021), None).iter().all(|s| s.year == 2021));
+}
+
+#[test]
+fn test_parse_options() {
+    let args: Vec<String> = ["2020", "9", "--alloc", "--param", "preamble=5"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    let mut params = Params::default();
+    params.set("preamble=5");
+    assert_eq!(
+        Options {
+            year: 2020,
+            day: Some(9),
+            params,
+            report: Report {
+                allocations: true,
+                json: false,
+            },
+        },
+        Options::parse(&args)
+    );
+}
+
+#[test]
+fn test_params() {
+    let mut params = Params::default();
+    assert_eq!(25, params.get("preamble", 25));
+
+    params.set("preamble=5");
+    params.set("rounds=10");
+    params.set("preamble=7");
+    assert_eq!(7, params.get("preamble", 25));
+    assert_eq!(10, params.get("rounds", 2020));
+    assert_eq!("preamble=7,rounds=10", params.to_string());
+    assert_eq!("", Params::default().to_string());
+}
+
+#[test]
+fn test_answer_to_json() {
+    let answer = Answer {
+        year: 2021,
+        day: 5,
+        part: 1,
+        answer: "say \"hi\"\\n".to_string(),
+        allocations: Some(Allocations {
+            count: 3,
+            bytes: 120,
+            peak: 80,
+        }),
+    };
+    assert_eq!(
+        r#"{"year":2021,"day":5,"part":1,"answer":"say \"hi\"\\n","allocations":{"count":3,"bytes":120,"peak":80}}"#,
+        answer.to_json()
+    );
+
+    let untracked = Answer {
+        allocations: None,
+        ..answer
+    };
+    assert!(untracked.to_json().ends_with(r#""allocations":null}"#));
+}
@@ -0,0 +1,352 @@
+use grid::Grid;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Everything a search found out on its way, from which the shortest path to the goal, or to any
+/// other state it reached, can be pieced back together
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct Search<S> {
+    /// The cheapest known cost to reach each state
+    costs: HashMap<S, usize>,
+    /// The state each state was first reached from along a cheapest path
+    parents: HashMap<S, S>,
+    /// How many different cheapest paths lead to each state
+    ways: HashMap<S, usize>,
+    goal: Option<S>,
+    expanded: usize,
+}
+
+#[allow(dead_code)]
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new(start: S) -> Search<S> {
+        let mut search = Search {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+            ways: HashMap::new(),
+            goal: None,
+            expanded: 0,
+        };
+        search.costs.insert(start.clone(), 0);
+        search.ways.insert(start, 1);
+        search
+    }
+
+    /// Note that `next` can be reached from `from` for `cost` in total, returning whether that's
+    /// cheaper than any way found before
+    fn reach(&mut self, from: &S, next: S, cost: usize) -> bool {
+        let ways = self.ways[from];
+        match self.costs.get(&next) {
+            Some(known) if *known < cost => false,
+            Some(known) if *known == cost => {
+                *self.ways.get_mut(&next).unwrap() += ways;
+                false
+            }
+            _ => {
+                self.costs.insert(next.clone(), cost);
+                self.parents.insert(next.clone(), from.clone());
+                self.ways.insert(next, ways);
+                true
+            }
+        }
+    }
+
+    /// The goal the search stopped at, if it found one
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of the cheapest path to the goal
+    pub fn goal_cost(&self) -> Option<usize> {
+        self.goal.as_ref().and_then(|g| self.cost_to(g))
+    }
+
+    /// A cheapest path from the start to the goal, both included
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.goal.as_ref().and_then(|g| self.path_to(g))
+    }
+
+    pub fn cost_to(&self, state: &S) -> Option<usize> {
+        self.costs.get(state).cloned()
+    }
+
+    /// A cheapest path from the start to `state`, both included
+    ///
+    /// Only states the search finished with are sure to be cheapest, which is all of them for a
+    /// search that ran without a goal.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.costs.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// How many different cheapest paths there are from the start to `state`
+    pub fn ways_to(&self, state: &S) -> usize {
+        self.ways.get(state).cloned().unwrap_or(0)
+    }
+
+    /// How many states the search reached, whether or not it got round to expanding them
+    pub fn visited(&self) -> usize {
+        self.costs.len()
+    }
+
+    /// How many states had their successors looked at
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+}
+
+/// Search outwards from `start` one step at a time until reaching a state `is_goal` accepts, for
+/// when every move costs the same
+///
+/// A goal that's never reached, such as `|_| false`, leaves the search covering every reachable
+/// state.
+#[allow(dead_code)]
+pub fn bfs<S, I, F, G>(start: S, mut successors: F, mut is_goal: G) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from(vec![start]);
+    while let Some(state) = queue.pop_front() {
+        // Checked on the way out rather than in so every path to the goal has been counted
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        search.expanded += 1;
+
+        let cost = search.costs[&state] + 1;
+        for next in successors(&state) {
+            if search.reach(&state, next.clone(), cost) {
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Search from `start` cheapest first until reaching a state `is_goal` accepts, where
+/// `successors` gives each state reachable in one move along with what that move costs
+#[allow(dead_code)]
+pub fn dijkstra<S, I, F, G>(start: S, successors: F, is_goal: G) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    astar(start, successors, |_| 0, is_goal)
+}
+
+/// Like `dijkstra`, but heading first for the states `heuristic` guesses are closest to the goal
+///
+/// The heuristic must never guess more than the real remaining cost, or the path found might not
+/// be the cheapest, and for paths to be counted properly it shouldn't drop by more than the cost of
+/// any one move and every move must cost something.
+#[allow(dead_code)]
+pub fn astar<S, I, F, H, G>(
+    start: S,
+    mut successors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+    F: FnMut(&S) -> I,
+    H: FnMut(&S) -> usize,
+    G: FnMut(&S) -> bool,
+{
+    let mut search = Search::new(start.clone());
+
+    // The heap holds indices into `states` so the states themselves don't need to be ordered. Ties
+    // on estimated total go to whichever is cheapest so far, so every cheapest route into a state
+    // is counted before it's expanded.
+    let mut states = vec![start.clone()];
+    let mut frontier = BinaryHeap::new();
+    frontier.push(Reverse((heuristic(&start), 0, 0)));
+
+    while let Some(Reverse((_, cost, index))) = frontier.pop() {
+        let state = states[index].clone();
+        if cost > search.costs[&state] {
+            // Already expanded from a cheaper path
+            continue;
+        }
+        if is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        search.expanded += 1;
+
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if search.reach(&state, next.clone(), next_cost) {
+                frontier.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+    search
+}
+
+/// The shortest route across a grid from `start` to `goal`, moving up, down, left or right onto
+/// any cell `passable` allows
+#[allow(dead_code)]
+pub fn grid_path<T, P>(
+    grid: &Grid<T>,
+    start: (usize, usize),
+    goal: (usize, usize),
+    mut passable: P,
+) -> Search<(usize, usize)>
+where
+    P: FnMut(&T) -> bool,
+{
+    let distance = |(x, y): &(usize, usize)| {
+        (*x as isize - goal.0 as isize).unsigned_abs()
+            + (*y as isize - goal.1 as isize).unsigned_abs()
+    };
+    astar(
+        start,
+        |(x, y)| {
+            grid.neighbours_4(*x, *y)
+                .filter(|(_, cell)| passable(cell))
+                .map(|(p, _)| (p, 1))
+                .collect::<Vec<_>>()
+        },
+        distance,
+        |p| *p == goal,
+    )
+}
+
+/// The lowest point of a function that's convex over the integers `low..=high`, along with its
+/// value there, found by binary searching on the slope
+///
+/// Comparing each midpoint with its right hand neighbour says which side of the minimum it's on,
+/// as a convex function can only level off at its minimum, so ties go to the leftmost minimum.
+pub fn minimise_convex<T, F>(mut low: isize, mut high: isize, mut f: F) -> (isize, T)
+where
+    T: Ord,
+    F: FnMut(isize) -> T,
+{
+    assert!(low <= high, "Empty range {}..={}", low, high);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if f(mid) <= f(mid + 1) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    (low, f(low))
+}
+
+#[cfg(test)]
+fn maze() -> Grid<char> {
+    let lines: Vec<String> = ["S...#...", ".##.#.#.", ".#..#.#.", ".#.##.#.", "......#G"]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+    Grid::parse(&lines, |c| c)
+}
+
+#[test]
+fn test_bfs() {
+    // Counting up to 10 in steps of two or three
+    let search = bfs(0, |n| vec![n + 2, n + 3], |n| *n == 10);
+    assert_eq!(Some(4), search.goal_cost());
+    assert_eq!(10, *search.goal_path().unwrap().last().unwrap());
+    // 2+2+3+3 in any order
+    assert_eq!(6, search.ways_to(&10));
+    assert_eq!(1, search.ways_to(&4));
+
+    let everything = bfs(0, |n| if *n < 5 { vec![n + 1] } else { vec![] }, |_| false);
+    assert_eq!(None, everything.goal());
+    assert_eq!(6, everything.visited());
+    assert_eq!(Some(vec![0, 1, 2, 3]), everything.path_to(&3));
+    assert_eq!(None, everything.path_to(&7));
+}
+
+#[test]
+fn test_dijkstra() {
+    // A direct but expensive route and two cheaper ones round the side
+    let edges: HashMap<char, Vec<(char, usize)>> = vec![
+        ('a', vec![('d', 10), ('b', 2), ('c', 3)]),
+        ('b', vec![('d', 4)]),
+        ('c', vec![('d', 3)]),
+    ]
+    .into_iter()
+    .collect();
+    let search = dijkstra(
+        'a',
+        |s| edges.get(s).cloned().unwrap_or_default(),
+        |s| *s == 'd',
+    );
+    assert_eq!(Some(6), search.goal_cost());
+    assert_eq!(2, search.ways_to(&'d'));
+    assert_eq!(Some(vec!['a', 'b', 'd']), search.goal_path());
+}
+
+#[test]
+fn test_grid_path() {
+    let maze = maze();
+    let search = grid_path(&maze, (0, 0), (7, 4), |c| *c != '#');
+    assert_eq!(Some(19), search.goal_cost());
+    let path = search.goal_path().unwrap();
+    assert_eq!(20, path.len());
+    assert!(path.iter().all(|(x, y)| maze[(*x, *y)] != '#'));
+
+    // The walls stop anything getting through
+    assert_eq!(None, grid_path(&maze, (0, 0), (7, 4), |c| *c == '.').goal());
+}
+
+#[test]
+fn test_astar_expands_less() {
+    let open = Grid::from_fn(30, 30, |_, _| '.');
+    // Searching blind spreads out in every direction, not just towards the goal
+    let (start, goal) = ((15, 15), (19, 18));
+    let guided = grid_path(&open, start, goal, |_| true);
+    let blind = bfs(
+        start,
+        |(x, y)| {
+            open.neighbours_4(*x, *y)
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>()
+        },
+        |p| *p == goal,
+    );
+    assert_eq!(guided.goal_cost(), blind.goal_cost());
+    assert!(guided.expanded() < blind.expanded());
+
+    // Every route that only ever goes right or down is a shortest one
+    assert_eq!(
+        20,
+        grid_path(&open, (0, 0), (3, 3), |_| true).ways_to(&(3, 3))
+    );
+}
+
+#[test]
+fn test_minimise_convex() {
+    assert_eq!((7, 3), minimise_convex(-100, 100, |x| (x - 7).pow(2) + 3));
+    assert_eq!((-100, 0), minimise_convex(-100, 100, |x| x + 100));
+    assert_eq!((5, 5), minimise_convex(5, 5, |x| x));
+    // Flat at the bottom, from 2 to 4
+    assert_eq!(
+        (2, 0),
+        minimise_convex(-10, 10, |x| (x - 2).min(0).abs() + (x - 4).max(0))
+    );
+}
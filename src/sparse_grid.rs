@@ -0,0 +1,189 @@
+use point::Point2;
+use std::collections::HashMap;
+use std::ops::AddAssign;
+
+/// A grid with no fixed size, storing only the cells that have been set, for puzzles whose points
+/// are scattered over a large or unknown area
+///
+/// The bounding box of every set cell is kept up to date as cells come and go, ready for rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+    bounds: Option<(Point2, Point2)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    fn extend_bounds(&mut self, p: Point2) {
+        self.bounds = Some(match self.bounds {
+            None => (p, p),
+            Some((min, max)) => (
+                Point2::new(min.x.min(p.x), min.y.min(p.y)),
+                Point2::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+        });
+    }
+
+    pub fn insert(&mut self, p: Point2, value: T) -> Option<T> {
+        self.extend_bounds(p);
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point2) -> Option<T> {
+        let removed = self.cells.remove(&p)?;
+        // Only a cell on the edge of the bounding box can shrink it
+        if let Some((min, max)) = self.bounds {
+            if p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y {
+                self.bounds = None;
+                let remaining: Vec<Point2> = self.cells.keys().cloned().collect();
+                remaining.into_iter().for_each(|p| self.extend_bounds(p));
+            }
+        }
+        Some(removed)
+    }
+
+    pub fn get(&self, p: Point2) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point2) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    /// The cell at `p`, setting it first if it wasn't already
+    pub fn get_or_insert_with<F>(&mut self, p: Point2, value: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        self.extend_bounds(p);
+        self.cells.entry(p).or_insert_with(value)
+    }
+
+    pub fn contains(&self, p: Point2) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The top left and bottom right corners of the smallest box holding every set cell
+    pub fn bounds(&self) -> Option<(Point2, Point2)> {
+        self.bounds
+    }
+
+    /// Every set cell, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&Point2, &T)> {
+        self.cells.iter()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// How many set cells match the predicate
+    pub fn count<P>(&self, mut predicate: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells.values().filter(|v| predicate(v)).count()
+    }
+
+    /// Draw the bounding box a row at a time, with the formatter deciding how each cell looks,
+    /// including the cells that were never set
+    pub fn render<F>(&self, mut cell: F) -> String
+    where
+        F: FnMut(Option<&T>) -> char,
+    {
+        match self.bounds {
+            None => String::new(),
+            Some((min, max)) => (min.y..=max.y)
+                .map(|y| {
+                    (min.x..=max.x)
+                        .map(|x| cell(self.get(Point2::new(x, y))))
+                        .collect::<String>()
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+        }
+    }
+}
+
+impl<T: AddAssign + Default> SparseGrid<T> {
+    /// Add to the cell at `p`, treating it as the default if it wasn't set yet
+    pub fn add(&mut self, p: Point2, amount: T) {
+        *self.get_or_insert_with(p, T::default) += amount;
+    }
+}
+
+impl SparseGrid<usize> {
+    /// How many times each point turns up
+    pub fn count_points<I>(points: I) -> SparseGrid<usize>
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        let mut counts = SparseGrid::new();
+        points.into_iter().for_each(|p| counts.add(p, 1));
+        counts
+    }
+}
+
+#[test]
+fn test_counting_and_bounds() {
+    let counts = SparseGrid::count_points(vec![
+        Point2::new(2, -1),
+        Point2::new(0, 1),
+        Point2::new(2, -1),
+    ]);
+    assert_eq!(2, counts.len());
+    assert_eq!(Some(&2), counts.get(Point2::new(2, -1)));
+    assert_eq!(1, counts.count(|c| *c > 1));
+    assert_eq!(
+        Some((Point2::new(0, -1), Point2::new(2, 1))),
+        counts.bounds()
+    );
+}
+
+#[test]
+fn test_remove_shrinks_bounds() {
+    let mut grid = SparseGrid::new();
+    grid.insert(Point2::new(0, 0), 'a');
+    grid.insert(Point2::new(5, 5), 'b');
+    grid.insert(Point2::new(2, 3), 'c');
+
+    assert_eq!(Some('b'), grid.remove(Point2::new(5, 5)));
+    assert_eq!(Some((Point2::new(0, 0), Point2::new(2, 3))), grid.bounds());
+    assert_eq!(None, grid.remove(Point2::new(5, 5)));
+
+    grid.remove(Point2::new(0, 0));
+    grid.remove(Point2::new(2, 3));
+    assert_eq!(None, grid.bounds());
+    assert!(grid.is_empty());
+}
+
+#[test]
+fn test_render() {
+    let mut grid = SparseGrid::count_points(vec![Point2::new(-1, 0), Point2::new(1, 1)]);
+    grid.add(Point2::new(1, 1), 2);
+    assert_eq!(
+        "1..\n..3",
+        grid.render(|c| c.map_or('.', |c| std::char::from_digit(*c as u32, 10).unwrap()))
+    );
+    assert_eq!("", SparseGrid::<usize>::new().render(|_| '.'));
+}
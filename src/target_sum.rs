@@ -0,0 +1,247 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
+
+/// Two of the values adding up to `target`, with `reuse` allowing the same entry to be counted
+/// twice
+///
+/// Remembers every value seen so far, so it's one pass however many values there are.
+pub fn two_sum(values: &[isize], target: isize, reuse: bool) -> Option<(isize, isize)> {
+    let mut seen = HashSet::new();
+    for &value in values {
+        if seen.contains(&(target - value)) || (reuse && value * 2 == target) {
+            return Some((target - value, value));
+        }
+        seen.insert(value);
+    }
+    None
+}
+
+/// `k` of the values adding up to `target`, smallest first, with `reuse` allowing the same entry
+/// to be counted more than once
+///
+/// Pairs are found with `two_sum`, anything bigger by sorting, fixing all but two of the values
+/// and closing in on the last two from either end of what's left.
+pub fn k_sum(values: &[isize], k: usize, target: isize, reuse: bool) -> Option<Vec<isize>> {
+    match k {
+        0 => Some(vec![]).filter(|_| target == 0),
+        1 => values.iter().find(|&&v| v == target).map(|&v| vec![v]),
+        2 => two_sum(values, target, reuse).map(|(a, b)| vec![a.min(b), a.max(b)]),
+        _ => {
+            let mut sorted = values.to_vec();
+            sorted.sort_unstable();
+            sorted_k_sum(&sorted, k, target, reuse)
+        }
+    }
+}
+
+fn sorted_k_sum(sorted: &[isize], k: usize, target: isize, reuse: bool) -> Option<Vec<isize>> {
+    if k == 2 {
+        if sorted.is_empty() {
+            return None;
+        }
+        let (mut low, mut high) = (0, sorted.len() - 1);
+        while low < high || (reuse && low == high) {
+            let sum = sorted[low] + sorted[high];
+            if sum == target {
+                return Some(vec![sorted[low], sorted[high]]);
+            } else if sum < target {
+                low += 1;
+            } else if high == 0 {
+                break;
+            } else {
+                high -= 1;
+            }
+        }
+        return None;
+    }
+
+    for (i, &first) in sorted.iter().enumerate() {
+        // Equal values would only find the same sums again
+        if i > 0 && sorted[i - 1] == first {
+            continue;
+        }
+        let rest = if reuse {
+            &sorted[i..]
+        } else {
+            &sorted[i + 1..]
+        };
+        if let Some(mut found) = sorted_k_sum(rest, k - 1, target - first, reuse) {
+            found.insert(0, first);
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// The sums of every pair of entries in a sliding window of the last `size` values, kept up to
+/// date as values are pushed rather than worked out again for each window
+#[derive(Debug, Clone)]
+pub struct PairSums {
+    size: usize,
+    window: VecDeque<isize>,
+    sums: HashMap<isize, usize>,
+}
+
+impl PairSums {
+    pub fn new(size: usize) -> PairSums {
+        PairSums {
+            size,
+            window: VecDeque::with_capacity(size),
+            sums: HashMap::new(),
+        }
+    }
+
+    /// Add a value to the window, pushing the oldest out if it's already full
+    pub fn push(&mut self, value: isize) {
+        if self.is_full() {
+            let oldest = self.window.pop_front().unwrap();
+            for other in &self.window {
+                let count = self.sums.get_mut(&(oldest + other)).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    self.sums.remove(&(oldest + other));
+                }
+            }
+        }
+        for other in &self.window {
+            *self.sums.entry(value + other).or_insert(0) += 1;
+        }
+        self.window.push_back(value);
+    }
+
+    /// Whether two different entries in the window add up to `sum`
+    pub fn contains(&self, sum: isize) -> bool {
+        self.sums.contains_key(&sum)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.window.len() == self.size
+    }
+}
+
+/// Every run of one or more consecutive values adding up to `target`, ordered by where they start
+/// then where they end
+///
+/// Works with negative values too, by looking for earlier prefix sums exactly `target` short of
+/// each later one.
+#[allow(dead_code)]
+pub fn contiguous_sums(values: &[isize], target: isize) -> Vec<Range<usize>> {
+    let mut starts: HashMap<isize, Vec<usize>> = HashMap::new();
+    let mut ranges = vec![];
+    let mut prefix = 0;
+    for (end, value) in values.iter().enumerate() {
+        starts.entry(prefix).or_default().push(end);
+        prefix += value;
+        if let Some(found) = starts.get(&(prefix - target)) {
+            ranges.extend(found.iter().map(|&start| start..end + 1));
+        }
+    }
+    ranges.sort_unstable_by_key(|r| (r.start, r.end));
+    ranges
+}
+
+/// The same as `contiguous_sums` for values that are never negative, which can slide a window
+/// along instead, growing it while it's short of `target` and shrinking it from the front after
+pub fn contiguous_sums_non_negative(values: &[isize], target: isize) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let (mut end, mut sum) = (0, 0);
+    for start in 0..values.len() {
+        if end < start {
+            end = start;
+            sum = 0;
+        }
+        while end < values.len() && (sum < target || end == start) {
+            sum += values[end];
+            end += 1;
+        }
+        if sum == target && end > start {
+            ranges.push(start..end);
+            // Zeros just after the window add nothing, so each of them ends another match
+            ranges.extend(
+                (end..values.len())
+                    .take_while(|&zero| values[zero] == 0)
+                    .map(|zero| start..zero + 1),
+            );
+        }
+        if end > start {
+            sum -= values[start];
+        }
+    }
+    ranges
+}
+
+#[test]
+fn test_two_sum() {
+    let values = [1721, 979, 366, 299, 675, 1456];
+    assert_eq!(Some((1721, 299)), two_sum(&values, 2020, false));
+    assert_eq!(None, two_sum(&values, 1958, false));
+    assert_eq!(Some((979, 979)), two_sum(&values, 1958, true));
+}
+
+#[test]
+fn test_k_sum() {
+    let values = [1721, 979, 366, 299, 675, 1456];
+    assert_eq!(Some(vec![299, 1721]), k_sum(&values, 2, 2020, false));
+    assert_eq!(Some(vec![366, 675, 979]), k_sum(&values, 3, 2020, false));
+    assert_eq!(None, k_sum(&values, 3, 3 * 675, false));
+    assert_eq!(Some(vec![675, 675, 675]), k_sum(&values, 3, 3 * 675, true));
+    assert_eq!(
+        Some(vec![299, 366, 675, 1721]),
+        k_sum(&values, 4, 3061, false)
+    );
+    assert_eq!(Some(vec![]), k_sum(&values, 0, 0, false));
+    assert_eq!(None, k_sum(&[], 3, 0, true));
+}
+
+#[test]
+fn test_pair_sums() {
+    let mut window = PairSums::new(3);
+    for value in [1, 2, 4] {
+        window.push(value);
+    }
+    assert!(window.is_full());
+    assert!(window.contains(3) && window.contains(6) && !window.contains(2));
+
+    // Pushing 8 drops the 1, and with it the sums it made
+    window.push(8);
+    assert!(!window.contains(3) && !window.contains(5));
+    assert!(window.contains(6) && window.contains(12));
+}
+
+#[test]
+fn test_contiguous_sums() {
+    let values = [35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182];
+    assert_eq!(vec![2..6], contiguous_sums(&values, 127));
+    assert_eq!(vec![2..6], contiguous_sums_non_negative(&values, 127));
+    assert_eq!(
+        vec![0..1, 1..3, 3..5],
+        contiguous_sums(&[5, 1, 4, -3, 8], 5)
+    );
+}
+
+#[test]
+fn test_contiguous_sums_agree() {
+    use rng::Rng;
+
+    let mut rng = Rng::new(46);
+    for _ in 0..200 {
+        let values: Vec<isize> = (0..rng.below(12)).map(|_| rng.range(0, 4)).collect();
+        let target = rng.range(0, 8);
+        let brute_force: Vec<Range<usize>> = (0..values.len())
+            .flat_map(|start| (start + 1..=values.len()).map(move |end| start..end))
+            .filter(|r| values[r.clone()].iter().sum::<isize>() == target)
+            .collect();
+        assert_eq!(
+            brute_force,
+            contiguous_sums(&values, target),
+            "{:?}",
+            values
+        );
+        assert_eq!(
+            brute_force,
+            contiguous_sums_non_negative(&values, target),
+            "{:?}",
+            values
+        );
+    }
+}